@@ -4,15 +4,21 @@ use clap::Parser;
 use frontend::terminal_frontend::Frontend;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use command_management::{
     autocompletion::autocompletion_data_list_build::build_command_autocompletion_data_list,
     command_engine::CommandEngine, command_executor::CommandExecutor,
+    commands::command_enum::Command, output::output_type::OutputType,
 };
 use data_caches::podcast_state_cache_builder::build_podcast_state_cache;
 use data_transport::{DataReceiver, DataSender};
 use path_providing::{default_path_provider::DefaultPathProvider, path_provider::PathProvider};
 use podcast_player::players::gstreamer_mp3_player::GStreamerMp3Player;
+use rss_management::http_settings::{
+    HttpSettings, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_FEED_TIMEOUT_SECS,
+    DEFAULT_MAX_PARALLEL_FETCHES, DEFAULT_MAX_RETRIES,
+};
 
 use tokio::sync::mpsc::channel;
 
@@ -23,6 +29,54 @@ struct Args {
     /// Shows a popup with basic information about how to use the app
     #[clap(short, long)]
     show_first_start_popup: bool,
+    /// Does not use the network, and builds the library from the local copies of the feeds
+    #[clap(long)]
+    offline: bool,
+    /// Interval between two refreshes of the feeds, in minutes. 0 disables refreshing
+    #[clap(long, value_name = "MINUTES", default_value_t = 60)]
+    refresh_interval: u64,
+    /// Subscribes to all feeds of the given OPML file, then exits
+    #[clap(long, value_name = "PATH")]
+    import_opml: Option<String>,
+    /// Exports all subscriptions to the given OPML file, then exits
+    #[clap(long, value_name = "PATH")]
+    export_opml: Option<String>,
+    /// Maximum duration of the connection to a server, in seconds
+    #[clap(long, value_name = "SECONDS", default_value_t = DEFAULT_CONNECT_TIMEOUT_SECS)]
+    connect_timeout: u64,
+    /// Maximum duration of the download of a feed, in seconds
+    #[clap(long, value_name = "SECONDS", default_value_t = DEFAULT_FEED_TIMEOUT_SECS)]
+    feed_timeout: u64,
+    /// User-Agent header sent with every request
+    #[clap(long)]
+    user_agent: Option<String>,
+    /// Proxy used for HTTP and HTTPS requests. Defaults to the proxies set in the environment
+    #[clap(long, value_name = "URL")]
+    proxy: Option<String>,
+    /// Maximum number of feeds downloaded at the same time
+    #[clap(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_PARALLEL_FETCHES)]
+    max_parallel_fetches: usize,
+    /// Number of times a request is retried after a network or server failure
+    #[clap(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_RETRIES)]
+    max_retries: u32,
+}
+
+impl Args {
+    fn build_http_settings(&self) -> HttpSettings {
+        let default_settings = HttpSettings::default();
+        HttpSettings {
+            connect_timeout: Duration::from_secs(self.connect_timeout),
+            feed_timeout: Duration::from_secs(self.feed_timeout),
+            user_agent: self
+                .user_agent
+                .clone()
+                .unwrap_or(default_settings.user_agent),
+            proxy: self.proxy.clone(),
+            max_parallel_fetches: self.max_parallel_fetches,
+            max_retries: self.max_retries,
+            retry_backoff: default_settings.retry_backoff,
+        }
+    }
 }
 
 fn build_data_transfer_endpoints<T>(slots: usize) -> (DataSender<T>, DataReceiver<T>) {
@@ -31,6 +85,7 @@ fn build_data_transfer_endpoints<T>(slots: usize) -> (DataSender<T>, DataReceive
 }
 
 async fn build_app_components<Drawer: frontend::ui_drawers::ui_drawer::UiDrawer + Default>(
+    cli: &Args,
 ) -> (CommandEngine, Frontend<Drawer>, AutocompleterMessageProxy) {
     let path_provider = Arc::new(DefaultPathProvider {});
     let mp3_player = GStreamerMp3Player::build(path_provider.clone()).await;
@@ -50,6 +105,14 @@ async fn build_app_components<Drawer: frontend::ui_drawers::ui_drawer::UiDrawer
         Some(notifications_sender.clone()),
     )
     .await;
+    core.lock().await.set_offline(cli.offline);
+    core.lock()
+        .await
+        .set_http_settings(cli.build_http_settings())
+        .expect("Invalid HTTP settings");
+    if cli.refresh_interval != 0 {
+        BusinessCore::spawn_refresher(core.clone(), Duration::from_secs(cli.refresh_interval * 60));
+    }
 
     let executor = CommandExecutor::new(core, Some(autocompletion_request_sender.clone()));
     let command_engine = CommandEngine::new(
@@ -84,6 +147,40 @@ async fn build_app_components<Drawer: frontend::ui_drawers::ui_drawer::UiDrawer
     (command_engine, frontend, autocompleter)
 }
 
+/// Runs the OPML commands given on the command line without starting the UI
+async fn run_opml_commands(cli: &Args) {
+    let path_provider = Arc::new(DefaultPathProvider {});
+    let mp3_player = GStreamerMp3Player::build(path_provider.clone()).await;
+    let core = BusinessCore::new_in_arc(mp3_player, path_provider, None).await;
+    core.lock().await.set_offline(cli.offline);
+    if let Err(e) = core
+        .lock()
+        .await
+        .set_http_settings(cli.build_http_settings())
+    {
+        eprintln!("Invalid HTTP settings : {}", e);
+        return;
+    }
+    let mut executor = CommandExecutor::new(core, None);
+    executor.initialize().await;
+
+    let mut commands = vec![];
+    if let Some(path) = &cli.import_opml {
+        commands.push(Command::ImportOpml(path.clone()));
+    }
+    if let Some(path) = &cli.export_opml {
+        commands.push(Command::ExportOpml(path.clone()));
+    }
+
+    for command in commands {
+        match executor.execute_command(command).await {
+            Ok(OutputType::RawString(s)) => println!("{}", s.trim_end()),
+            Ok(_) => (),
+            Err(e) => eprintln!("{}", e.message.clone().unwrap_or_else(|| e.to_string())),
+        }
+    }
+}
+
 fn is_first_start() -> bool {
     let path_provider = DefaultPathProvider {};
 
@@ -92,11 +189,16 @@ fn is_first_start() -> bool {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Args::parse();
+    if cli.import_opml.is_some() || cli.export_opml.is_some() {
+        run_opml_commands(&cli).await;
+        return Ok(());
+    }
+
     let (mut command_engine, mut frontend, mut autocompleter) = build_app_components::<
         frontend::ui_drawers::minimalistic_ui_drawer::MinimalisticUiDrawer,
-    >()
+    >(&cli)
     .await;
-    let cli = Args::parse();
     let is_first_start = is_first_start() || cli.show_first_start_popup;
     let command_frontend_future = frontend.run(is_first_start);
     let command_engine_future = command_engine.run();
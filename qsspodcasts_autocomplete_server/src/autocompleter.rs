@@ -153,14 +153,14 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
-    use command_management::commands::command_enum::Command;
+    use command_management::commands::command_enum::{Command, EpisodeFilter};
 
     #[test]
     fn test_autocomplete_command() {
         let autocompleter = Autocompleter::new(vec![
             AutocompletionCommandData::new(Command::Exit, None),
             AutocompletionCommandData::new(Command::ListPodcasts, None),
-            AutocompletionCommandData::new(Command::ListEpisodes(None), None),
+            AutocompletionCommandData::new(Command::ListEpisodes(EpisodeFilter::default()), None),
         ]);
         let mut command_to_be_completed = String::from("ex");
         let autocomplete_choices = autocompleter.autocomplete_command(&command_to_be_completed);
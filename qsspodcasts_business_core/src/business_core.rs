@@ -1,22 +1,43 @@
 use std::borrow::BorrowMut;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Error as IoError};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use fs_utils::{progression_read_utils, write_utils};
-use log::{error, info};
+use fs_utils::{progression_read_utils, renaming_utils, write_utils};
+use futures::future::join_all;
+use log::{debug, error, info, warn};
 use podcast_management::data_objects::hashable::Hashable;
 use podcast_player::enums::player_state::Mp3PlayerState;
 use podcast_player::player_error;
 use podcast_player::traits::PlayerObserver;
 use tokio::sync::Mutex as TokioMutex;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 
 use rss_management::{
+    channel_tuple::ChannelTuple,
+    http_settings::{HttpClient, HttpSettings},
     local_storage::{
-        application_dir_initializer::ApplicationDirInitializer, rss_provider::RssProvider,
+        application_dir_initializer::ApplicationDirInitializer, credential_store::CredentialStore,
+        feed_cache::FeedCache, rss_provider::RssProvider,
     },
-    url_storage::file_url_storer::FileUrlStorer,
+    opml::{
+        opml_import_report::OpmlImportReport, opml_outline::OpmlOutline,
+        opml_reader::read_opml_file, opml_writer::write_opml_file,
+    },
+    rss_feed_reading::{
+        chapters::{fetch_json_chapters, read_id3_chapters},
+        feed::Feed,
+        feed_archive::get_archive_pages,
+        feed_diagnostic::FeedDiagnostic,
+        feed_discovery::{discover_feeds, FeedCandidate},
+        feed_move::FeedMove,
+        feed_source::FeedSource,
+        utils::{diagnose_feed, get_feeds, local_file_path},
+    },
+    url_storage::json_url_storer::JsonUrlStorer,
 };
 
 use crate::event_type::EventType;
@@ -24,23 +45,40 @@ use crate::notification::Notification;
 use data_transport::DataSender;
 use path_providing::default_path_provider::PathProvider;
 use podcast_download::podcast_downloader::PodcastDownloader;
+use podcast_management::data_objects::chapter::{
+    find_next_chapter, find_previous_chapter, Chapter,
+};
+use podcast_management::data_objects::episode_details::EpisodeDetails;
+use podcast_management::data_objects::podcast_details::PodcastDetails;
 use podcast_management::data_objects::podcast_episode::PodcastEpisode;
 use podcast_management::{
     builders::podcast_builder::PodcastBuilder, data_objects::podcast::Podcast,
-    podcast_library::PodcastLibrary,
+    episode_filter::ListeningStates, playback_queue::PlaybackQueue,
+    podcast_library::PodcastLibrary, smart_playlists::SmartPlaylists,
 };
 use podcast_player::{player_error::PlayerError, players::mp3_player::Mp3Player};
 
+/// Chapters of the selected episode, identified by its persistent key
+#[derive(Default)]
+struct EpisodeChapters {
+    key: String,
+    chapters: Vec<Chapter>,
+}
+
 // TODO : Add concept of InitializedBusinessCore which is returned by BusinessCore::initialize, and consumes self
 pub struct BusinessCore {
     application_dir_initializer: ApplicationDirInitializer,
-    rss_provider: RssProvider<FileUrlStorer>,
+    rss_provider: RssProvider<JsonUrlStorer>,
     podcast_builder: PodcastBuilder,
     podcast_downloader: PodcastDownloader,
     player: Arc<TokioMutex<dyn Mp3Player + Send + Sync>>,
     pub podcast_library: Arc<TokioMutex<PodcastLibrary>>,
     path_provider: Arc<dyn PathProvider + Send + Sync>,
     notifications_sender: Option<DataSender<Notification>>,
+    playback_queue: PlaybackQueue,
+    smart_playlists: SmartPlaylists,
+    /// Chapters of the selected episode, which may be downloaded after it is selected
+    chapters: Arc<TokioMutex<EpisodeChapters>>,
 }
 
 impl BusinessCore {
@@ -50,10 +88,26 @@ impl BusinessCore {
         notifications_sender: Option<DataSender<Notification>>,
     ) -> BusinessCore {
         let podcast_library = Arc::new(TokioMutex::new(PodcastLibrary::new()));
+        let queue_file_path = path_provider.queue_file_path();
+        let playback_queue = PlaybackQueue::load(queue_file_path.clone()).unwrap_or_else(|e| {
+            error!("Could not read the playback queue : {}", e);
+            PlaybackQueue::new(queue_file_path)
+        });
+        let playlists_file_path = path_provider.playlists_file_path();
+        let smart_playlists =
+            SmartPlaylists::load(playlists_file_path.clone()).unwrap_or_else(|e| {
+                error!("Could not read the playlists : {}", e);
+                SmartPlaylists::new(playlists_file_path)
+            });
         let core = BusinessCore {
-            rss_provider: RssProvider::new(FileUrlStorer::new(PathBuf::from(
-                path_provider.rss_feed_list_file_path().to_str().unwrap(),
-            ))),
+            rss_provider: RssProvider::new(
+                JsonUrlStorer::new(
+                    path_provider.subscriptions_file_path(),
+                    path_provider.rss_feed_list_file_path(),
+                ),
+                FeedCache::new(path_provider.feed_cache_dir_path()),
+                CredentialStore::new(path_provider.credentials_file_path()),
+            ),
             podcast_builder: PodcastBuilder::new(),
             podcast_downloader: PodcastDownloader::new(path_provider.clone()),
             player: mp3_player,
@@ -63,6 +117,9 @@ impl BusinessCore {
             },
             path_provider,
             notifications_sender,
+            playback_queue,
+            smart_playlists,
+            chapters: Arc::new(TokioMutex::new(EpisodeChapters::default())),
         };
         // TODO: plug behavior on state change of player ? Probably not here
         core
@@ -136,12 +193,26 @@ impl BusinessCore {
                 "Could not find podcast matching hash",
             ));
         }
-        let url = podcast.unwrap().link;
+        let url = podcast.unwrap().feed_url;
         if let Err(e) = self.rss_provider.delete_url(&url) {
             self.send_notification(Notification::Message("Deletion of URL failed".to_string()))
                 .await;
             return Err(e);
         };
+        match self.smart_playlists.remove_podcast(&url) {
+            Ok(removed_playlists) if !removed_playlists.is_empty() => {
+                self.send_notification(Notification::Message(format!(
+                    "Playlists only made of this podcast deleted : {}",
+                    removed_playlists.join(", ")
+                )))
+                .await
+            }
+            Ok(_) => {}
+            Err(e) => error!(
+                "Could not remove the deleted feed from the playlists : {}",
+                e
+            ),
+        }
         self.send_notification(Notification::Message(
             "RSS feed deletion successful".to_string(),
         ))
@@ -149,29 +220,640 @@ impl BusinessCore {
         Ok(())
     }
 
+    /// Makes every request for the feed whose URL is given in parameter use HTTP basic authentication
+    pub async fn set_feed_basic_auth(
+        &mut self,
+        url: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<(), IoError> {
+        let result = self
+            .rss_provider
+            .get_credential_store()
+            .set_basic_auth(url, username, password);
+        self.notify_credentials_update(url, &result).await;
+        result
+    }
+
+    /// Makes every request for the feed whose URL is given in parameter carry an extra header
+    pub async fn set_feed_header(
+        &mut self,
+        url: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<(), IoError> {
+        let result = self
+            .rss_provider
+            .get_credential_store()
+            .set_header(url, name, value);
+        self.notify_credentials_update(url, &result).await;
+        result
+    }
+
+    /// Sets the name displayed instead of the title of the podcast, or removes it if there is none
+    pub async fn set_podcast_alias(
+        &mut self,
+        url: &str,
+        alias: Option<&str>,
+    ) -> Result<(), IoError> {
+        let result = self.rss_provider.set_alias(url, alias);
+        let message = match &result {
+            Ok(_) => format!("Alias of feed {} updated", url),
+            Err(e) => format!("Could not update alias of feed {} : {}", url, e),
+        };
+        self.send_notification(Notification::Message(message)).await;
+        result
+    }
+
+    /// Pauses or resumes the subscription to the feed. Paused feeds are neither refreshed nor loaded at startup.
+    pub async fn set_podcast_paused(&mut self, url: &str, paused: bool) -> Result<(), IoError> {
+        let result = self.rss_provider.set_paused(url, paused);
+        let message = match (&result, paused) {
+            (Ok(_), true) => format!("Feed {} paused, it will not be refreshed anymore", url),
+            (Ok(_), false) => format!(
+                "Feed {} resumed, it will be fetched at the next refresh",
+                url
+            ),
+            (Err(e), _) => format!("Could not update feed {} : {}", url, e),
+        };
+        self.send_notification(Notification::Message(message)).await;
+        result
+    }
+
+    /// Returns the aliases chosen by the user, indexed by the URL of their feed
+    pub fn get_podcast_aliases(&mut self) -> HashMap<String, String> {
+        match self.rss_provider.get_subscriptions() {
+            Ok(subscriptions) => subscriptions
+                .into_iter()
+                .filter_map(|s| s.alias.map(|a| (s.url, a)))
+                .collect(),
+            Err(e) => {
+                warn!("Could not read subscriptions metadata : {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    pub async fn clear_feed_credentials(&mut self, url: &str) -> Result<(), IoError> {
+        let result = self
+            .rss_provider
+            .get_credential_store()
+            .delete_credentials(url);
+        self.notify_credentials_update(url, &result).await;
+        result
+    }
+
+    async fn notify_credentials_update(&mut self, url: &str, result: &Result<(), IoError>) {
+        let message = match result {
+            Ok(_) => format!("Credentials of feed {} updated", url),
+            Err(e) => format!("Could not update credentials of feed {} : {}", url, e),
+        };
+        self.send_notification(Notification::Message(message)).await;
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.rss_provider.set_offline(offline);
+    }
+
+    /// Applies the HTTP settings to the downloads of feeds and episodes, unless they are invalid.
+    ///
+    /// Both share the same clients, so that their connections are reused.
+    pub fn set_http_settings(
+        &mut self,
+        http_settings: HttpSettings,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let http_client = HttpClient::new(http_settings)?;
+        self.podcast_downloader.set_http_client(http_client.clone());
+        self.rss_provider.set_http_client(http_client);
+        Ok(())
+    }
+
+    fn build_podcast(
+        podcast_builder: &PodcastBuilder,
+        channel: &ChannelTuple,
+        source: FeedSource,
+    ) -> Podcast {
+        let (mut podcast, skipped_items) = match &channel.1 {
+            Feed::Rss(c) => podcast_builder.build_with_report(c),
+            Feed::Atom(f) => podcast_builder.build_from_atom_with_report(f),
+        };
+        for (name, reason) in &skipped_items {
+            warn!("Item {} of feed {} skipped : {}", name, channel.0, reason);
+        }
+        podcast.set_feed_url(channel.0);
+        podcast.is_stale = source == FeedSource::Cache;
+        podcast
+    }
+
     pub async fn load_feed(&mut self, url: &str) -> Result<(), ()> {
-        let feeds = self.rss_provider.get_all_feeds().await;
-        let channel = feeds.0.iter().find(|c| c.0 == url);
+        let channel = self.rss_provider.get_feed(url).await;
         if channel.is_none() {
             error!("Could not find channel matching URL {}", url);
             return Err(());
         }
-        let podcast = self.podcast_builder.build(&channel.unwrap().1);
+        let (channel, source) = channel.unwrap();
+        let podcast = Self::build_podcast(&self.podcast_builder, &channel, source);
         self.podcast_library.lock().await.push(podcast);
+        self.apply_feed_moves().await;
         Ok(())
     }
 
+    /// Subscribes to the new URL of the feeds that moved, and notifies about it.
+    ///
+    /// Progress and finished markers are indexed by episode hash, which does not depend on the
+    /// URL of the feed, so they are kept as is.
+    async fn apply_feed_moves(&mut self) {
+        let feed_moves = self.rss_provider.apply_feed_moves();
+        for feed_move in feed_moves {
+            if let Err(e) = self
+                .smart_playlists
+                .replace_podcast(&feed_move.old_url, &feed_move.new_url)
+            {
+                error!("Could not update the playlists of the moved feed : {}", e);
+            }
+            let title = self
+                .podcast_library
+                .lock()
+                .await
+                .update_feed_url(&feed_move.old_url, &feed_move.new_url)
+                .unwrap_or(feed_move.old_url);
+            self.send_notification(Notification::Message(format!(
+                "Feed of {} moved to {}",
+                title, feed_move.new_url
+            )))
+            .await;
+        }
+    }
+
+    pub async fn import_opml(&mut self, path: &Path) -> Result<OpmlImportReport, IoError> {
+        let outlines = match read_opml_file(path) {
+            Ok(o) => o,
+            Err(e) => {
+                self.send_notification(Notification::Message(format!(
+                    "Reading of OPML file {} failed",
+                    path.display()
+                )))
+                .await;
+                return Err(e);
+            }
+        };
+        let report = self.rss_provider.import_outlines(outlines).await;
+
+        let mut failed_feeds = vec![];
+        for url in &report.imported_urls {
+            if self.load_feed(url).await.is_err() {
+                failed_feeds.push(url.clone());
+            }
+        }
+        if !failed_feeds.is_empty() {
+            self.send_notification(Notification::Message(format!(
+                "Failed to download feeds for following urls : {}",
+                failed_feeds.join(", ")
+            )))
+            .await;
+        }
+        self.send_notification(Notification::Message(format!(
+            "OPML import done, {} feed(s) added",
+            report.imported_urls.len()
+        )))
+        .await;
+        Ok(report)
+    }
+
+    /// Writes all subscriptions in an OPML file, and returns the number of exported feeds
+    pub async fn export_opml(&mut self, path: &Path) -> Result<usize, IoError> {
+        let subscriptions = self.rss_provider.get_subscriptions()?;
+        let library = self.podcast_library.lock().await;
+        let outlines = subscriptions
+            .into_iter()
+            .map(|s| {
+                let title = s.alias.or_else(|| {
+                    library
+                        .podcasts()
+                        .iter()
+                        .find(|p| p.feed_url == s.url)
+                        .map(|p| p.title.clone())
+                });
+                OpmlOutline::new(title, Some(s.url))
+            })
+            .collect::<Vec<OpmlOutline>>();
+        drop(library);
+
+        if let Err(e) = write_opml_file(path, &outlines) {
+            self.send_notification(Notification::Message(format!(
+                "Writing of OPML file {} failed",
+                path.display()
+            )))
+            .await;
+            return Err(e);
+        }
+        Ok(outlines.len())
+    }
+
+    /// Lists the items of the feed that cannot be turned into episodes, along with the reason why
+    fn collect_rejected_items(feed: &Feed) -> Vec<(String, String)> {
+        let podcast_builder = PodcastBuilder::new();
+        match feed {
+            Feed::Rss(channel) => podcast_builder.build_with_report(channel).1,
+            Feed::Atom(atom_feed) => podcast_builder.build_from_atom_with_report(atom_feed).1,
+        }
+    }
+
+    /// Finds the feeds available on a URL, which can either be the one of a feed or of a web page advertising feeds.
+    ///
+    /// In offline mode, the URL is assumed to be the one of a feed.
+    pub async fn discover_feeds(&self, url: &str) -> Result<Vec<FeedCandidate>, String> {
+        if self.rss_provider.is_offline() {
+            return Ok(vec![FeedCandidate {
+                url: url.to_owned(),
+                title: None,
+            }]);
+        }
+        let credentials = self.rss_provider.get_credentials(url);
+        discover_feeds(
+            url,
+            credentials.as_ref(),
+            self.rss_provider.get_http_client(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Downloads every subscribed feed again, bypassing the cache, and reports how it went for each of them
+    pub async fn check_feeds(&self) -> Vec<FeedDiagnostic> {
+        let urls = self.rss_provider.get_urls().await;
+        let credentials = self.rss_provider.get_all_credentials();
+        let http_client = self.rss_provider.get_http_client();
+        let diagnostics = join_all(
+            urls.iter()
+                .map(|url| diagnose_feed(url, credentials.get(url), http_client)),
+        )
+        .await;
+        diagnostics
+            .into_iter()
+            .map(|(mut diagnostic, feed)| {
+                if let Some(feed) = feed {
+                    diagnostic.rejected_items = Self::collect_rejected_items(&feed);
+                }
+                diagnostic
+            })
+            .collect()
+    }
+
+    fn read_file_names(dir: &Path) -> HashSet<String> {
+        match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().to_str().map(|n| n.to_owned()))
+                .collect(),
+            Err(e) => {
+                debug!("Could not read directory {} : {}", dir.display(), e);
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Returns the persistent keys of the episodes that are finished, and of the ones that have a saved progression
+    pub fn get_listening_states(&self) -> ListeningStates {
+        ListeningStates {
+            finished: Self::read_file_names(&self.path_provider.finished_podcasts_dir_path()),
+            in_progress: Self::read_file_names(&self.path_provider.podcast_progresses_dir_path()),
+        }
+    }
+
+    /// Gathers what is known about the episode, including whether it is downloaded and how far it was listened to
+    pub async fn get_episode_details(&self, episode: &PodcastEpisode) -> EpisodeDetails {
+        let key = episode.persistent_key();
+        let selected_episode = self.player.lock().await.get_selected_episode().await;
+        let is_selected = match selected_episode {
+            Some(e) => e.read().await.persistent_key() == key,
+            None => false,
+        };
+        // The progression of the selected episode is only written on pause
+        let progression = if is_selected {
+            self.player
+                .lock()
+                .await
+                .get_selected_episode_progression()
+                .await
+                .map(|p| p.into())
+        } else {
+            progression_read_utils::read_progression_in_file(
+                self.path_provider.podcast_progress_file_path(&key),
+            )
+            .await
+        };
+        EpisodeDetails {
+            episode: episode.clone(),
+            is_downloaded: self.path_provider.compute_episode_path(episode).exists(),
+            is_finished: self
+                .path_provider
+                .compute_finished_podcast_file_path(&key)
+                .exists(),
+            progression,
+        }
+    }
+
+    /// Summarizes the podcast whose hash is given, or returns None if there is no such podcast
+    pub async fn get_podcast_details(&self, hash: &str) -> Option<PodcastDetails> {
+        let states = self.get_listening_states();
+        let library = self.podcast_library.lock().await;
+        let podcast = library.podcasts().iter().find(|p| p.hash() == hash)?;
+        let keys = podcast
+            .episodes
+            .iter()
+            .map(|e| e.persistent_key())
+            .collect::<Vec<String>>();
+        Some(PodcastDetails {
+            podcast: podcast.shallow_copy(),
+            episodes_count: podcast.episodes.len(),
+            downloaded_count: podcast
+                .episodes
+                .iter()
+                .filter(|e| self.path_provider.compute_episode_path(e).exists())
+                .count(),
+            finished_count: keys.iter().filter(|k| states.finished.contains(*k)).count(),
+            in_progress_count: keys
+                .iter()
+                .filter(|k| states.in_progress.contains(*k) && !states.finished.contains(*k))
+                .count(),
+        })
+    }
+
+    /// Returns the persistent keys of all the episodes of the library
+    fn episode_keys(library: &PodcastLibrary) -> Vec<String> {
+        library
+            .podcasts()
+            .iter()
+            .flat_map(|p| p.episodes.iter())
+            .map(|e| e.persistent_key())
+            .collect()
+    }
+
+    /// Adds the episodes to the ones listed at the previous launches, so that they are not
+    /// considered new at the next launch
+    async fn record_seen_episodes(path: PathBuf, keys: Vec<String>) {
+        let content = tokio::fs::read_to_string(&path).await;
+        let file_exists = content.is_ok();
+        let mut seen_episodes = content
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.to_owned())
+            .collect::<HashSet<String>>();
+        let previous_count = seen_episodes.len();
+        seen_episodes.extend(keys);
+        if seen_episodes.len() == previous_count && file_exists {
+            return;
+        }
+        let mut content = seen_episodes
+            .into_iter()
+            .collect::<Vec<String>>()
+            .join("\n");
+        content.push('\n');
+        if let Err(e) = tokio::fs::write(&path, content).await {
+            error!("Could not record the episodes seen : {}", e);
+        }
+    }
+
+    /// Renames the progression files, finished markers and audio files named after the legacy hash of
+    /// episodes of the library, so that they are found under the persistent key of the episodes.
+    ///
+    /// This runs at every startup rather than once : files of episodes that are not in the library are left as is,
+    /// and are renamed once the episodes are back. Legacy hashes shared by several episodes are ambiguous, so their
+    /// files are never renamed.
+    pub async fn rename_legacy_episode_files(&mut self) {
+        let library = self.podcast_library.lock().await;
+        let episodes = library
+            .podcasts()
+            .iter()
+            .flat_map(|p| p.episodes.iter())
+            .cloned()
+            .collect::<Vec<PodcastEpisode>>();
+        drop(library);
+
+        let mut new_names: HashMap<String, String> = HashMap::new();
+        let mut ambiguous_hashes: HashSet<String> = HashSet::new();
+        for episode in &episodes {
+            let legacy_hash = episode.legacy_hash();
+            if new_names
+                .insert(legacy_hash.clone(), episode.persistent_key())
+                .is_some()
+            {
+                ambiguous_hashes.insert(legacy_hash);
+            }
+        }
+        new_names.retain(|h, _| !ambiguous_hashes.contains(h));
+
+        if let Err(e) = renaming_utils::rename_files(
+            &self.path_provider.podcast_progresses_dir_path(),
+            &new_names,
+        ) {
+            error!("Renaming of legacy progression files failed : {}", e);
+        }
+        let finished_keys = match renaming_utils::rename_files(
+            &self.path_provider.finished_podcasts_dir_path(),
+            &new_names,
+        ) {
+            Ok(k) => k,
+            Err(e) => {
+                error!("Renaming of legacy finished markers failed : {}", e);
+                vec![]
+            }
+        };
+        // States of the episodes may have been read under their legacy hash already
+        for key in finished_keys {
+            self.send_notification(Notification::Event(EventType::PodcastFinished(key)))
+                .await;
+        }
+
+        for episode in episodes
+            .iter()
+            .filter(|e| !ambiguous_hashes.contains(&e.legacy_hash()))
+        {
+            let new_path = self.path_provider.compute_episode_path(episode);
+            let legacy_path = self
+                .path_provider
+                .download_dir_path()
+                .join(episode.get_legacy_file_name().replace(' ', "_"));
+            if legacy_path.exists() && !new_path.exists() {
+                if let Err(e) = std::fs::rename(&legacy_path, &new_path) {
+                    error!(
+                        "Renaming of legacy audio file {} failed : {}",
+                        legacy_path.display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Fetches the feed of the podcast whose hash is given again, or all feeds if there is none, and replaces
+    /// the matching podcasts of the library.
+    ///
+    /// Returns the hashes of the podcasts refreshed and of their episodes.
+    pub async fn refresh(&mut self, hash: Option<&str>) -> Result<Vec<String>, IoError> {
+        let podcasts = match hash {
+            Some(hash) => {
+                let url = match self.podcast_library.lock().await.search_podcast(hash) {
+                    Some(p) => p.feed_url,
+                    None => {
+                        return Err(IoError::new(
+                            io::ErrorKind::NotFound,
+                            "Could not find podcast matching hash",
+                        ))
+                    }
+                };
+                let (channel, source) = match self.rss_provider.get_feed(&url).await {
+                    Some(c) => c,
+                    None => {
+                        return Err(IoError::new(
+                            io::ErrorKind::Other,
+                            format!("Could not fetch feed on URL {}", url),
+                        ))
+                    }
+                };
+                vec![Self::build_podcast(&self.podcast_builder, &channel, source)]
+            }
+            None => {
+                let (channels, failed_urls) = self.rss_provider.get_all_feeds().await;
+                let podcasts = channels
+                    .iter()
+                    .map(|(channel, source)| {
+                        Self::build_podcast(&self.podcast_builder, channel, *source)
+                    })
+                    .collect::<Vec<Podcast>>();
+                if !failed_urls.is_empty() {
+                    self.send_notification(Notification::Message(format!(
+                        "Failed to download feeds for following urls : {}",
+                        failed_urls.join(", ")
+                    )))
+                    .await;
+                }
+                podcasts
+            }
+        };
+
+        let mut hashes = vec![];
+        let mut new_episodes = vec![];
+        let mut library = self.podcast_library.lock().await;
+        for podcast in podcasts {
+            hashes.push(podcast.hash());
+            hashes.extend(podcast.episodes.iter().map(|e| e.hash()));
+            let title = podcast.title.clone();
+            let count = library.replace(podcast);
+            if count != 0 {
+                new_episodes.push((title, count));
+            }
+        }
+        let episode_keys = Self::episode_keys(&library);
+        drop(library);
+        Self::record_seen_episodes(self.path_provider.seen_episodes_file_path(), episode_keys)
+            .await;
+
+        for (podcast, count) in new_episodes {
+            self.send_notification(Notification::Event(EventType::NewEpisodes {
+                podcast,
+                count,
+            }))
+            .await;
+        }
+        self.apply_feed_moves().await;
+        Ok(hashes)
+    }
+
+    /// Downloads the older pages of the feed of the podcast, and merges the episodes they contain in the library.
+    ///
+    /// The core is not locked while the pages are being downloaded, since there can be many of them.
+    /// Returns the hashes of the podcast and of its episodes. The older episodes are only kept until the podcast
+    /// is refreshed or the library is built again.
+    pub async fn load_archive(
+        core: Arc<TokioMutex<BusinessCore>>,
+        hash: &str,
+    ) -> Result<Vec<String>, IoError> {
+        let mut locked_core = core.lock().await;
+        let podcast = match locked_core
+            .podcast_library
+            .lock()
+            .await
+            .search_podcast(hash)
+        {
+            Some(p) => p,
+            None => {
+                return Err(IoError::new(
+                    io::ErrorKind::NotFound,
+                    "Could not find podcast matching hash",
+                ))
+            }
+        };
+        let url = podcast.feed_url;
+        if locked_core.rss_provider.is_offline() && local_file_path(&url).is_none() {
+            return Err(IoError::new(
+                io::ErrorKind::Other,
+                "Archives cannot be loaded in offline mode",
+            ));
+        }
+
+        locked_core
+            .send_notification(Notification::Message(format!(
+                "Loading archive of {}...",
+                podcast.title
+            )))
+            .await;
+        let credentials = locked_core.rss_provider.get_credentials(&url);
+        let http_client = locked_core.rss_provider.get_http_client().clone();
+        drop(locked_core);
+
+        let pages = get_archive_pages(&url, credentials.as_ref(), &http_client)
+            .await
+            .map_err(|e| IoError::new(io::ErrorKind::Other, e.to_string()))?;
+
+        let mut locked_core = core.lock().await;
+        let mut library = locked_core.podcast_library.lock().await;
+        let mut count = 0;
+        for page in pages {
+            let channel = (url.as_str(), page);
+            count += library.merge(Self::build_podcast(
+                &locked_core.podcast_builder,
+                &channel,
+                FeedSource::Network,
+            ));
+        }
+        let hashes = match library.podcasts().iter().find(|p| p.feed_url == url) {
+            Some(p) => std::iter::once(p.hash())
+                .chain(p.episodes.iter().map(|e| e.hash()))
+                .collect(),
+            None => vec![],
+        };
+        drop(library);
+
+        locked_core
+            .send_notification(Notification::Message(format!(
+                "{} older episodes of {} loaded",
+                count, podcast.title
+            )))
+            .await;
+        Ok(hashes)
+    }
+
     pub async fn build_podcasts(&mut self) {
         self.send_notification(Notification::Message("Building library...".to_string()))
             .await;
 
         let channels = self.rss_provider.get_all_feeds().await;
         let mut podcasts: Vec<Podcast> = vec![];
-        for channel in &channels.0 {
+        for (channel, source) in &channels.0 {
             // TODO(mmiko) : Parallelize this
-            podcasts.push(self.podcast_builder.build(&channel.1))
+            podcasts.push(Self::build_podcast(&self.podcast_builder, channel, *source))
         }
-        self.podcast_library.lock().await.push(podcasts);
+        let stale_podcasts_count = podcasts.iter().filter(|p| p.is_stale).count();
+        let skipped_items_count: usize = podcasts.iter().map(|p| p.skipped_items_count).sum();
+        let mut library = self.podcast_library.lock().await;
+        library.push(podcasts);
+        let episode_keys = Self::episode_keys(&library);
+        let hash_collisions = library.find_hash_collisions();
+        drop(library);
+        Self::record_seen_episodes(self.path_provider.seen_episodes_file_path(), episode_keys)
+            .await;
         if !channels.1.is_empty() {
             let failed_feeds = channels.1.join(", ");
             self.send_notification(Notification::Message(format!(
@@ -180,23 +862,167 @@ impl BusinessCore {
             )))
             .await;
         }
+        if skipped_items_count != 0 {
+            self.send_notification(Notification::Message(format!(
+                "{} feed item(s) could not be turned into episodes, run check_feeds for details",
+                skipped_items_count
+            )))
+            .await;
+        }
+        if stale_podcasts_count != 0 {
+            self.send_notification(Notification::Message(format!(
+                "{} podcast(s) loaded from local cache, they may be outdated",
+                stale_podcasts_count
+            )))
+            .await;
+        }
+        for (hash, titles) in &hash_collisions {
+            warn!("Hash {} is shared by : {}", hash, titles.join(", "));
+        }
+        if !hash_collisions.is_empty() {
+            self.send_notification(Notification::Message(format!(
+                "{} hash(es) are shared by several items, use a longer prefix to pick an episode : {}",
+                hash_collisions.len(),
+                hash_collisions
+                    .iter()
+                    .map(|(h, _)| h.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )))
+            .await;
+        }
+        self.apply_feed_moves().await;
         self.send_notification(Notification::Message("Building library done".to_string()))
             .await;
     }
 
+    /// Spawns a task that fetches all subscribed feeds again every `period`, and merges
+    /// the new episodes in the library.
+    ///
+    /// The core is not locked while the feeds are being downloaded.
+    pub fn spawn_refresher(
+        core: Arc<TokioMutex<BusinessCore>>,
+        period: std::time::Duration,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately, and the library is built at startup anyway
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let mut locked_core = core.lock().await;
+                if locked_core.rss_provider.is_offline() {
+                    continue;
+                }
+                let urls = match locked_core.rss_provider.get_active_urls() {
+                    Ok(u) => u,
+                    Err(e) => {
+                        error!("Could not read subscriptions : {}", e);
+                        continue;
+                    }
+                };
+                let feed_cache = locked_core.rss_provider.get_feed_cache().clone();
+                let credentials = locked_core.rss_provider.get_all_credentials();
+                let http_client = locked_core.rss_provider.get_http_client().clone();
+                drop(locked_core);
+
+                debug!("Refreshing {} feeds", urls.len());
+                let urls = urls.iter().map(|u| u.as_str()).collect::<Vec<&str>>();
+                let (channels, _) =
+                    get_feeds(&urls, &feed_cache, &credentials, &http_client, false).await;
+                let podcast_builder = PodcastBuilder::new();
+                let podcasts = channels
+                    .iter()
+                    .map(|(channel, source, _)| {
+                        Self::build_podcast(&podcast_builder, channel, *source)
+                    })
+                    .collect::<Vec<Podcast>>();
+                let feed_moves = channels
+                    .into_iter()
+                    .filter_map(|(_, _, feed_move)| feed_move)
+                    .collect::<Vec<FeedMove>>();
+
+                let mut locked_core = core.lock().await;
+                locked_core.rss_provider.add_feed_moves(feed_moves);
+                locked_core.merge_podcasts(podcasts).await;
+            }
+        })
+    }
+
+    /// Merges refreshed podcasts in the library, and notifies about the new episodes
+    pub async fn merge_podcasts(&mut self, podcasts: Vec<Podcast>) {
+        // Subscriptions may have been deleted while feeds were being fetched
+        let urls = self.rss_provider.get_urls().await;
+        let podcasts = podcasts
+            .into_iter()
+            .filter(|p| urls.contains(&p.feed_url))
+            .collect::<Vec<Podcast>>();
+        let fetched_urls = podcasts
+            .iter()
+            .filter(|p| !p.is_stale)
+            .map(|p| p.feed_url.as_str())
+            .collect::<Vec<&str>>();
+        if let Err(e) = self.rss_provider.record_successful_fetches(&fetched_urls) {
+            error!("Could not record fetch dates of feeds : {}", e);
+        }
+
+        let mut library = self.podcast_library.lock().await;
+        let mut new_episodes = vec![];
+        for podcast in podcasts {
+            let title = podcast.title.clone();
+            let count = library.merge(podcast);
+            if count != 0 {
+                new_episodes.push((title, count));
+            }
+        }
+        let episode_keys = Self::episode_keys(&library);
+        drop(library);
+        Self::record_seen_episodes(self.path_provider.seen_episodes_file_path(), episode_keys)
+            .await;
+
+        for (podcast, count) in new_episodes {
+            self.send_notification(Notification::Event(EventType::NewEpisodes {
+                podcast,
+                count,
+            }))
+            .await;
+        }
+        self.apply_feed_moves().await;
+    }
+
     pub async fn download_episode(&mut self, episode: &PodcastEpisode) -> Result<(), ()> {
         self.send_notification(Notification::Message(format!(
             "Downloading \"{}\"",
             episode.title
         )))
         .await;
-        if (self.podcast_downloader.download_episode(episode).await).is_err() {
+        if self.rss_provider.is_offline()
+            && !self.path_provider.compute_episode_path(episode).exists()
+        {
+            self.send_notification(Notification::Message(
+                "Episode not downloaded, cannot download it in offline mode".to_string(),
+            ))
+            .await;
+            return Err(());
+        }
+        let credentials = self.rss_provider.get_credentials(&episode.feed_url);
+        if (self
+            .podcast_downloader
+            .download_episode(episode, credentials.as_ref())
+            .await)
+            .is_err()
+        {
             self.send_notification(Notification::Message("Downloading failed".to_string()))
                 .await;
             return Err(());
         }
         self.send_notification(Notification::Message("Downloading successful".to_string()))
             .await;
+        self.send_notification(Notification::Event(EventType::EpisodeDownloaded(
+            episode.persistent_key(),
+        )))
+        .await;
 
         Ok(())
     }
@@ -213,6 +1039,107 @@ impl BusinessCore {
             .expect("Writing notification in channel failed");
     }
 
+    /// Reads the chapters of the episode from the ID3 tag of its audio file, and otherwise downloads the chapters file
+    /// referenced by its feed in the background.
+    ///
+    /// Downloaded chapters are sent in a `ChaptersLoaded` event, unless another episode was selected in the meantime.
+    async fn load_chapters(&mut self, episode: &PodcastEpisode) {
+        let key = episode.persistent_key();
+        let chapters = read_id3_chapters(&self.path_provider.compute_episode_path(episode));
+        *self.chapters.lock().await = EpisodeChapters {
+            key: key.clone(),
+            chapters: chapters.clone(),
+        };
+        self.send_notification(Notification::Event(EventType::ChaptersLoaded(
+            chapters.clone(),
+        )))
+        .await;
+        let url = match &episode.chapters_url {
+            Some(u) if chapters.is_empty() && !self.rss_provider.is_offline() => u.clone(),
+            _ => return,
+        };
+
+        let http_client = self.rss_provider.get_http_client().clone();
+        let episode_chapters = self.chapters.clone();
+        let mut notifications_sender = self.notifications_sender.clone();
+        let title = episode.title.clone();
+        tokio::spawn(async move {
+            let chapters = match fetch_json_chapters(&url, &http_client).await {
+                Ok(c) => c,
+                Err(e) => {
+                    warn!("Could not get chapters of {} from {} : {}", title, url, e);
+                    return;
+                }
+            };
+            let mut episode_chapters = episode_chapters.lock().await;
+            if episode_chapters.key != key {
+                return;
+            }
+            episode_chapters.chapters = chapters.clone();
+            // Notifying before releasing the chapters keeps them from being sent after the ones of the next episode
+            if let Some(sender) = notifications_sender.as_mut() {
+                sender
+                    .send(Notification::Event(EventType::ChaptersLoaded(chapters)))
+                    .await
+                    .expect("Writing notification in channel failed");
+            }
+        });
+    }
+
+    pub async fn get_chapters(&self) -> Vec<Chapter> {
+        self.chapters.lock().await.chapters.clone()
+    }
+
+    async fn seek_to_chapter(
+        &mut self,
+        find_chapter: fn(&[Chapter], std::time::Duration) -> Option<&Chapter>,
+    ) -> Result<Chapter, IoError> {
+        let chapters = self.get_chapters().await;
+        if chapters.is_empty() {
+            return Err(IoError::new(
+                io::ErrorKind::NotFound,
+                "The selected episode has no chapters",
+            ));
+        }
+        let position = match self
+            .player
+            .lock()
+            .await
+            .get_selected_episode_progression()
+            .await
+        {
+            Some(p) => *p.inner_ref(),
+            None => return Err(IoError::new(io::ErrorKind::NotFound, "No episode selected")),
+        };
+        let chapter = match find_chapter(&chapters, position) {
+            Some(c) => c.clone(),
+            None => return Err(IoError::new(io::ErrorKind::NotFound, "No such chapter")),
+        };
+        let start = chrono::Duration::from_std(chapter.start)
+            .map_err(|e| IoError::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        self.player
+            .lock()
+            .await
+            .absolute_seek(start)
+            .await
+            .map_err(|e| IoError::new(io::ErrorKind::Other, e.to_string()))?;
+        self.send_notification(Notification::Message(format!(
+            "Chapter : {}",
+            chapter.title
+        )))
+        .await;
+        Ok(chapter)
+    }
+
+    pub async fn next_chapter(&mut self) -> Result<Chapter, IoError> {
+        self.seek_to_chapter(find_next_chapter).await
+    }
+
+    /// Goes back to the beginning of the current chapter, or to the previous one if the current one just started
+    pub async fn previous_chapter(&mut self) -> Result<Chapter, IoError> {
+        self.seek_to_chapter(find_previous_chapter).await
+    }
+
     pub async fn seek(&mut self, duration: chrono::Duration) -> Result<(), PlayerError> {
         self.player.lock().await.relative_seek(duration).await
     }
@@ -249,7 +1176,10 @@ impl BusinessCore {
         Ok(())
     }
 
-    async fn save_current_podcast_progression(&self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Writes the progression of the selected episode, and returns its persistent key along with the position saved
+    async fn save_current_podcast_progression(
+        &self,
+    ) -> Result<(String, std::time::Duration), Box<dyn std::error::Error>> {
         if self
             .player
             .lock()
@@ -263,7 +1193,7 @@ impl BusinessCore {
                 player_error::ErrorKind::NoEpisodeSelected,
             )));
         }
-        let hash = self
+        let key = self
             .player
             .lock()
             .await
@@ -272,9 +1202,9 @@ impl BusinessCore {
             .unwrap()
             .read()
             .await
-            .hash();
+            .persistent_key();
         let mut progression_file_path = self.path_provider.podcast_progresses_dir_path();
-        progression_file_path.push(hash);
+        progression_file_path.push(&key);
         let current_progression = self
             .player
             .lock()
@@ -289,7 +1219,7 @@ impl BusinessCore {
         )
         .await
         .expect("Writing of progression in file failed");
-        Ok(())
+        Ok((key, *current_progression.inner_ref()))
     }
 
     pub async fn pause(&mut self) -> Result<(), PlayerError> {
@@ -311,7 +1241,12 @@ impl BusinessCore {
         // Actually pause the player
         if !self.player.lock().await.is_paused() {
             self.player.lock().await.pause();
-            self.save_current_podcast_progression().await.unwrap();
+            let (key, position) = self.save_current_podcast_progression().await.unwrap();
+            self.send_notification(Notification::Event(EventType::ProgressionSaved {
+                key,
+                position,
+            }))
+            .await;
             self.send_notification(Notification::Message("Player paused".to_string()))
                 .await;
         } else {
@@ -326,21 +1261,20 @@ impl BusinessCore {
         Ok(())
     }
 
-    async fn create_mark_as_finished_marker_file(&mut self, hash: &str) -> Result<(), ()> {
-        let cloned_hash = hash.to_owned();
-        let finished_podcast_file_path =
-            self.path_provider.compute_finished_podcast_file_path(hash);
+    async fn create_mark_as_finished_marker_file(&mut self, key: &str) -> Result<(), ()> {
+        let cloned_key = key.to_owned();
+        let finished_podcast_file_path = self.path_provider.compute_finished_podcast_file_path(key);
         let _ =
             write_utils::open_or_create_file(finished_podcast_file_path.to_str().unwrap()).unwrap();
         self.send_notification(Notification::Message("Episode finished".to_owned()))
             .await;
-        self.send_notification(Notification::Event(EventType::PodcastFinished(cloned_hash)))
+        self.send_notification(Notification::Event(EventType::PodcastFinished(cloned_key)))
             .await;
         Ok(())
     }
 
-    async fn delete_progression_file(&mut self, hash: &str) -> Result<(), ()> {
-        let progression_file_path = self.path_provider.podcast_progress_file_path(hash);
+    async fn delete_progression_file(&mut self, key: &str) -> Result<(), ()> {
+        let progression_file_path = self.path_provider.podcast_progress_file_path(key);
         let r = tokio::fs::remove_file(progression_file_path).await;
         match r {
             Ok(_) => {}
@@ -361,18 +1295,18 @@ impl BusinessCore {
 
     #[allow(unused_assignments)]
     pub async fn mark_current_podcast_as_finished(&mut self) -> Result<(), PlayerError> {
-        let mut hash: Option<String> = None;
+        let mut key: Option<String> = None;
         {
             let player = self.player.lock().await;
             let episode = player.get_selected_episode().await;
-            hash = if let Some(e) = episode {
-                Some(e.read().await.hash().clone())
+            key = if let Some(e) = episode {
+                Some(e.read().await.persistent_key())
             } else {
                 None
             };
         }
 
-        if hash.is_none() {
+        if key.is_none() {
             self.send_notification(Notification::Message(
                 "No episode currently selected".to_string(),
             ))
@@ -383,7 +1317,7 @@ impl BusinessCore {
             ));
         }
         match self
-            .create_mark_as_finished_marker_file(hash.as_ref().unwrap())
+            .create_mark_as_finished_marker_file(key.as_ref().unwrap())
             .await
         {
             Ok(_) => Ok(()),
@@ -392,12 +1326,38 @@ impl BusinessCore {
     }
 
     pub async fn select_episode(&mut self, episode: &PodcastEpisode) -> Result<(), PlayerError> {
-        let hash = episode.hash();
-        let path = self.path_provider.podcast_progress_file_path(&hash);
+        self.select_episode_inner(episode, true).await
+    }
+
+    /// Selects the episode, saving the progression of the one selected before if `save_progression` is set.
+    ///
+    /// It must not be set when the episode selected before is finished, since its progression file was deleted.
+    async fn select_episode_inner(
+        &mut self,
+        episode: &PodcastEpisode,
+        save_progression: bool,
+    ) -> Result<(), PlayerError> {
+        let key = episode.persistent_key();
+        let path = self.path_provider.podcast_progress_file_path(&key);
         let duration = progression_read_utils::read_progression_in_file(path).await;
 
-        if self.save_current_podcast_progression().await.is_err() {
-            log::info!("Did not save current podcast progression due to no episode being selected (probably)");
+        // The error is not Send, so it cannot be kept while notifying
+        let saved_progression = if save_progression {
+            self.save_current_podcast_progression().await.ok()
+        } else {
+            None
+        };
+        match saved_progression {
+            Some((key, position)) => {
+                self.send_notification(Notification::Event(EventType::ProgressionSaved {
+                    key,
+                    position,
+                }))
+                .await
+            }
+            None => {
+                log::info!("Did not save current podcast progression due to no episode being selected (probably)")
+            }
         }
         self.player.lock().await.reset_progression();
 
@@ -431,6 +1391,7 @@ impl BusinessCore {
                         .await
                         .expect("Seeking resuming position of podcast failed");
                 }
+                self.load_chapters(episode).await;
             }
             Err(ref e) => {
                 let error_kind = e.kind();
@@ -463,6 +1424,148 @@ impl BusinessCore {
         };
         r
     }
+    /// Adds the episode at the end of the queue
+    pub async fn queue_episode(&mut self, episode: &PodcastEpisode) -> Result<(), IoError> {
+        self.playback_queue.push(&episode.persistent_key())?;
+        self.send_notification(Notification::Message(format!(
+            "\"{}\" added to the queue",
+            episode.title
+        )))
+        .await;
+        Ok(())
+    }
+
+    pub fn unqueue_episode(&mut self, episode: &PodcastEpisode) -> Result<(), IoError> {
+        self.playback_queue.remove(&episode.persistent_key())
+    }
+
+    /// Moves the queued episode at the given index of the queue
+    pub fn move_queued_episode(
+        &mut self,
+        episode: &PodcastEpisode,
+        index: usize,
+    ) -> Result<(), IoError> {
+        self.playback_queue
+            .move_to(&episode.persistent_key(), index)
+    }
+
+    pub fn clear_queue(&mut self) -> Result<(), IoError> {
+        self.playback_queue.clear()
+    }
+
+    /// Returns the queued episodes, in order, skipping the ones that are not in the library anymore.
+    ///
+    /// Queued keys shared by several episodes of the library are reported, and only their first episode is returned.
+    pub async fn get_queued_episodes(&mut self) -> Vec<PodcastEpisode> {
+        let library = self.podcast_library.lock().await;
+        let mut ambiguous_keys = vec![];
+        let episodes = self
+            .playback_queue
+            .keys()
+            .iter()
+            .filter_map(|k| {
+                let episodes = library.find_episodes_by_key(k);
+                if episodes.len() > 1 {
+                    ambiguous_keys.push(k.clone());
+                }
+                episodes.into_iter().next()
+            })
+            .collect();
+        drop(library);
+        for key in ambiguous_keys {
+            self.notify_ambiguous_queued_episode(&key).await;
+        }
+        episodes
+    }
+
+    async fn notify_ambiguous_queued_episode(&mut self, key: &str) {
+        warn!(
+            "Queued episode {} matches several episodes of the library",
+            key
+        );
+        self.send_notification(Notification::Message(format!(
+            "Several episodes of the library share the key {} of a queued episode, only the first one is used",
+            key
+        )))
+        .await;
+    }
+
+    /// Plays the first episode of the queue, and removes it from the queue once it is selected.
+    ///
+    /// Queued episodes that are not in the library anymore are dropped.
+    pub async fn play_next(&mut self) -> Result<PodcastEpisode, IoError> {
+        self.play_queue_front(true).await
+    }
+
+    /// Same as `play_next`, the progression of the episode selected before being saved only if `save_progression` is set
+    async fn play_queue_front(
+        &mut self,
+        save_progression: bool,
+    ) -> Result<PodcastEpisode, IoError> {
+        let (key, episode) = loop {
+            let key = match self.playback_queue.keys().first() {
+                Some(k) => k.clone(),
+                None => return Err(IoError::new(io::ErrorKind::NotFound, "The queue is empty")),
+            };
+            let episodes = self.podcast_library.lock().await.find_episodes_by_key(&key);
+            if episodes.len() > 1 {
+                self.notify_ambiguous_queued_episode(&key).await;
+            }
+            match episodes.into_iter().next() {
+                Some(e) => break (key, e),
+                None => warn!("Queued episode {} not found in library, skipping it", key),
+            }
+            self.playback_queue.remove(&key)?;
+        };
+
+        if self.download_episode(&episode).await.is_err() {
+            return Err(IoError::new(
+                io::ErrorKind::Other,
+                format!("Could not download \"{}\"", episode.title),
+            ));
+        }
+        self.select_episode_inner(&episode, save_progression)
+            .await
+            .map_err(|e| IoError::new(io::ErrorKind::Other, e.to_string()))?;
+        self.playback_queue.remove(&key)?;
+        self.play()
+            .await
+            .map_err(|e| IoError::new(io::ErrorKind::Other, e.to_string()))?;
+        Ok(episode)
+    }
+
+    /// Puts the episodes at the front of the queue, before the ones already queued, and plays the first one
+    pub async fn play_episodes(
+        &mut self,
+        episodes: &[PodcastEpisode],
+    ) -> Result<PodcastEpisode, IoError> {
+        self.playback_queue
+            .insert_front(episodes.iter().map(|e| e.persistent_key()).collect())?;
+        self.play_next().await
+    }
+
+    pub fn get_playlist_names(&self) -> Vec<String> {
+        self.smart_playlists
+            .names()
+            .into_iter()
+            .map(|n| n.to_owned())
+            .collect()
+    }
+
+    /// Returns the options of the filter selecting the episodes of the playlist
+    pub fn get_playlist_definition(&self, name: &str) -> Option<Vec<String>> {
+        self.smart_playlists.get(name).map(|o| o.to_vec())
+    }
+
+    /// Saves the playlist, replacing the one of the same name if any
+    pub fn save_playlist(&mut self, name: &str, options: Vec<String>) -> Result<(), IoError> {
+        self.smart_playlists.insert(name, options)
+    }
+
+    pub fn delete_playlist(&mut self, name: &str) -> Result<(), IoError> {
+        self.smart_playlists.remove(name)
+    }
+
     pub async fn clean(&mut self) {
         if self
             .player
@@ -491,11 +1594,26 @@ impl BusinessCore {
 
 #[async_trait]
 impl PlayerObserver for BusinessCore {
-    async fn on_podcast_finished(&mut self, hash: &str) {
-        self.create_mark_as_finished_marker_file(hash)
-            .await
-            .unwrap();
-        self.delete_progression_file(hash).await.unwrap();
+    async fn on_podcast_finished(&mut self, key: &str) {
+        self.create_mark_as_finished_marker_file(key).await.unwrap();
+        self.delete_progression_file(key).await.unwrap();
+
+        if self.playback_queue.contains(key) {
+            if let Err(e) = self.playback_queue.remove(key) {
+                error!("Could not remove finished episode from the queue : {}", e);
+            }
+        }
+        if self.playback_queue.is_empty() {
+            return;
+        }
+        // The progression file of the finished episode was just deleted, and must not be written again
+        if let Err(e) = self.play_queue_front(false).await {
+            self.send_notification(Notification::Message(format!(
+                "Playing next episode of the queue failed : {}",
+                e
+            )))
+            .await;
+        }
     }
 }
 
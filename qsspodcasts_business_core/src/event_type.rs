@@ -1,8 +1,24 @@
+use podcast_management::data_objects::chapter::Chapter;
+
 // TODO: Add a more concrete hash type ?
 type Hash = String;
 
 #[derive(Debug)]
 pub enum EventType {
     PodcastFinished(Hash),
+    EpisodeDownloaded(Hash),
+    /// Position the listening of the episode was stopped at
+    ProgressionSaved {
+        key: Hash,
+        position: std::time::Duration,
+    },
     PodcastLaunched(String),
+    /// Chapters of the episode that was just selected, empty if it has none.
+    /// Sent again once they are downloaded, if they come from the chapters file referenced by the feed.
+    ChaptersLoaded(Vec<Chapter>),
+    /// Episodes released since the last time the feed of the podcast was fetched
+    NewEpisodes {
+        podcast: String,
+        count: usize,
+    },
 }
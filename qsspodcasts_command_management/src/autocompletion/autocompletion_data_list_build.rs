@@ -10,12 +10,27 @@ pub fn command_to_parameter(c: &Command) -> Option<CommandParameterType> {
     match c {
         Command::Help(_) => Some(CommandParameterType::CommandName),
         Command::AddRss(_) => Some(CommandParameterType::Url),
+        Command::AddFolder(_) => Some(CommandParameterType::FilePath),
         Command::DeleteRss(_) => Some(CommandParameterType::Hash),
+        Command::LoadArchive(_) => Some(CommandParameterType::Hash),
+        Command::Refresh(_) => Some(CommandParameterType::Hash),
         Command::Select(_) => Some(CommandParameterType::Hash),
+        Command::See(_) => Some(CommandParameterType::Hash),
         Command::Play(_) => Some(CommandParameterType::Hash),
         Command::ListEpisodes(_) => Some(CommandParameterType::Hash),
         Command::Advance(_) => Some(CommandParameterType::Duration),
         Command::GoBack(_) => Some(CommandParameterType::Duration),
+        Command::ImportOpml(_) => Some(CommandParameterType::FilePath),
+        Command::ExportOpml(_) => Some(CommandParameterType::FilePath),
+        Command::SetFeedAuth(..) => Some(CommandParameterType::Url),
+        Command::SetFeedHeader(..) => Some(CommandParameterType::Url),
+        Command::ClearFeedAuth(_) => Some(CommandParameterType::Url),
+        Command::SetAlias(..) => Some(CommandParameterType::Url),
+        Command::PauseFeed(_) => Some(CommandParameterType::Url),
+        Command::ResumeFeed(_) => Some(CommandParameterType::Url),
+        Command::QueueAdd(_) => Some(CommandParameterType::Hash),
+        Command::QueueRemove(_) => Some(CommandParameterType::Hash),
+        Command::QueueMove(..) => Some(CommandParameterType::Hash),
         _ => None,
     }
 }
@@ -6,6 +6,7 @@ pub enum CommandParameterType {
     Duration,
     CommandName,
     Url,
+    FilePath,
 }
 
 static AUTO_COMPLETIONABLE_PARAMETERS: Lazy<Vec<CommandParameterType>> = Lazy::new(|| {
@@ -9,6 +9,7 @@ pub enum ErrorKind {
     ExecutionFailed,
     UnhandledCommand,
     ObjectNotFound,
+    AmbiguousHash,
     DownloadFailed,
     SelectionFailed,
     BadParameterCount,
@@ -1,12 +1,15 @@
 use log::debug;
 use podcast_management::data_objects::hashable::Hashable;
 use podcast_management::data_objects::podcast::Podcast;
+use podcast_management::hash_resolution_error::HashResolutionError;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex as TokioMutex;
 use url::Url;
 
 use crate::command_error::{self, CommandError, ErrorKind as CommandErrorKind};
-use crate::commands::command_enum::Command;
+use crate::commands::command_enum::{Command, EpisodeFilter};
+use crate::commands::command_factories::parse_episode_filter;
 use crate::commands::helps::{
     command_help_library::CommandHelpLibrary,
     command_help_library_builder::get_command_help_library,
@@ -42,6 +45,7 @@ impl CommandExecutor {
         let mut core = self.core.lock().await;
         core.initialize();
         core.build_podcasts().await;
+        core.rename_legacy_episode_files().await;
     }
 
     async fn handle_play(&mut self, _: Command) -> Result<OutputType, CommandError> {
@@ -68,6 +72,26 @@ impl CommandExecutor {
         }
     }
 
+    fn build_hash_resolution_error(command: String, error: HashResolutionError) -> CommandError {
+        let kind = match error {
+            HashResolutionError::NotFound(_) => CommandErrorKind::ObjectNotFound,
+            HashResolutionError::Ambiguous(_, _) => CommandErrorKind::AmbiguousHash,
+        };
+        let message = error.to_string();
+        CommandError::new(Some(Box::new(error)), kind, Some(command), Some(message))
+    }
+
+    async fn resolve_podcast_hash(&self, prefix: &str) -> Result<String, HashResolutionError> {
+        let tmp_core = self.core.lock().await;
+        let hash = tmp_core
+            .podcast_library
+            .lock()
+            .await
+            .resolve_podcast_hash(prefix);
+        drop(tmp_core);
+        hash
+    }
+
     async fn update_autocompleter_hashes(&mut self, hashes: Vec<String>) -> Result<(), ()> {
         if let Some(sender) = self.autocompleter_command_sender.as_mut() {
             let res = sender
@@ -80,13 +104,20 @@ impl CommandExecutor {
     }
 
     async fn handle_list_podcasts(&mut self, _: Command) -> Result<OutputType, CommandError> {
-        let tmp_core = self.core.lock().await;
+        let mut tmp_core = self.core.lock().await;
+        let aliases = tmp_core.get_podcast_aliases();
         let podcast_library = tmp_core.podcast_library.lock().await;
-        let podcasts = &podcast_library.podcasts;
+        let podcasts = podcast_library.podcasts();
 
         let podcasts = podcasts
             .iter()
-            .map(|p| p.shallow_copy())
+            .map(|p| {
+                let mut podcast = p.shallow_copy();
+                if let Some(alias) = aliases.get(&p.feed_url) {
+                    podcast.title = alias.clone();
+                }
+                podcast
+            })
             .collect::<Vec<Podcast>>();
         drop(podcast_library);
         drop(tmp_core);
@@ -106,32 +137,21 @@ impl CommandExecutor {
     async fn handle_list_episodes(
         &mut self,
         _: Command,
-        hash: Option<String>,
+        filter: EpisodeFilter,
     ) -> Result<OutputType, CommandError> {
         let tmp_core = self.core.lock().await;
-        let podcast_library = tmp_core.podcast_library.lock().await;
-        let podcasts = &podcast_library.podcasts;
-
-        let episodes_iter = podcasts
-            .iter()
-            .flat_map(|p| p.episodes.clone())
-            .filter(|e| {
-                if hash.is_none() {
-                    return true;
-                }
-
-                let p = podcasts
-                    .iter()
-                    .find(|p| &p.hash() == hash.as_ref().unwrap());
-                let title = if let Some(t) = &p { &t.title } else { "" };
-
-                e.podcast_name == title
-            });
-
-        let mut episodes: Vec<PodcastEpisode> = episodes_iter.collect();
-        episodes.sort_by(|p1, p2| p1.pub_date.cmp(&p2.pub_date).reverse());
-        drop(podcast_library);
+        let states = tmp_core.get_listening_states();
+        let episodes = filter.apply(&*tmp_core.podcast_library.lock().await, &states);
         drop(tmp_core);
+        let episodes = match episodes {
+            Ok(e) => e,
+            Err(e) => {
+                return Err(Self::build_hash_resolution_error(
+                    "list_episodes".to_owned(),
+                    e,
+                ))
+            }
+        };
 
         let hashes = episodes.iter().map(|p| p.hash()).collect();
         self.update_autocompleter_hashes(hashes)
@@ -141,45 +161,87 @@ impl CommandExecutor {
         Ok(OutputType::Episodes(episodes))
     }
 
-    async fn search_episode(&self, hash: &str) -> Option<PodcastEpisode> {
+    async fn search_episode(&self, hash: &str) -> Result<PodcastEpisode, HashResolutionError> {
         let tmp_core = self.core.lock().await;
-        let episode = tmp_core.podcast_library.lock().await.search_episode(hash);
+        let episode = tmp_core.podcast_library.lock().await.resolve_episode(hash);
         drop(tmp_core);
         episode
     }
 
     async fn select_episode(&mut self, hash: &str) -> Result<OutputType, CommandError> {
-        if let Some(ep) = self.search_episode(hash).await {
-            if self.core.lock().await.download_episode(&ep).await.is_err() {
-                return Err(CommandError::new(
-                    None,
-                    CommandErrorKind::DownloadFailed,
-                    Some(format!("select {}", hash)),
-                    Some("Episode download failed".to_string()),
-                ));
+        match self.search_episode(hash).await {
+            Ok(ep) => {
+                if self.core.lock().await.download_episode(&ep).await.is_err() {
+                    return Err(CommandError::new(
+                        None,
+                        CommandErrorKind::DownloadFailed,
+                        Some(format!("select {}", hash)),
+                        Some("Episode download failed".to_string()),
+                    ));
+                }
+                if self.core.lock().await.select_episode(&ep).await.is_err() {
+                    return Err(CommandError::new(
+                        None,
+                        CommandErrorKind::SelectionFailed,
+                        Some(format!("select {}", hash)),
+                        Some("Episode selection failed".to_string()),
+                    ));
+                }
             }
-            if self.core.lock().await.select_episode(&ep).await.is_err() {
-                return Err(CommandError::new(
-                    None,
-                    CommandErrorKind::SelectionFailed,
-                    Some(format!("select {}", hash)),
-                    Some("Episode selection failed".to_string()),
-                ));
+            Err(e) => {
+                return Err(Self::build_hash_resolution_error(
+                    format!("select {}", hash),
+                    e,
+                ))
             }
-        } else {
-            return Err(CommandError::new(
-                None,
-                CommandErrorKind::ObjectNotFound,
-                Some(format!("select {}", hash)),
-                Some("Episode not found".to_string()),
-            ));
         }
         Ok(OutputType::None)
     }
 
     async fn add_rss(&mut self, url: &Url) -> Result<OutputType, CommandError> {
         let url = url.to_string();
-        if let Err(e) = self.core.lock().await.add_url(&url).await {
+        let candidates = match self.core.lock().await.discover_feeds(&url).await {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(CommandError::new(
+                    None,
+                    command_error::ErrorKind::ExecutionFailed,
+                    None,
+                    Some(format!("Could not look for feeds at URL {} : {}", url, e)),
+                ))
+            }
+        };
+        match candidates.len() {
+            0 => Err(CommandError::new(
+                None,
+                command_error::ErrorKind::ExecutionFailed,
+                None,
+                Some(format!("No RSS or Atom feed found at URL {}", url)),
+            )),
+            1 => self.subscribe(&candidates[0].url).await,
+            _ => Ok(OutputType::FeedCandidates(candidates)),
+        }
+    }
+
+    async fn handle_add_folder_command(&mut self, path: &str) -> Result<OutputType, CommandError> {
+        let path = match Path::new(path).canonicalize() {
+            Ok(p) if p.is_dir() => p,
+            _ => {
+                return Err(CommandError::new(
+                    None,
+                    CommandErrorKind::ExecutionFailed,
+                    Some("add_folder".to_owned()),
+                    Some(format!("{} is not a folder", path)),
+                ))
+            }
+        };
+        // Local folders are stored as file URLs, and read as feeds
+        let url = Url::from_directory_path(&path).unwrap().to_string();
+        self.subscribe(&url).await
+    }
+
+    async fn subscribe(&mut self, url: &str) -> Result<OutputType, CommandError> {
+        if let Err(e) = self.core.lock().await.add_url(url).await {
             return Err(CommandError::new(
                 Some(Box::new(e)),
                 command_error::ErrorKind::ExecutionFailed,
@@ -187,7 +249,7 @@ impl CommandExecutor {
                 Some("URL writing failed".to_string()),
             ));
         }
-        if self.core.lock().await.load_feed(&url).await.is_err() {
+        if self.core.lock().await.load_feed(url).await.is_err() {
             return Err(CommandError::new(
                 None,
                 command_error::ErrorKind::ExecutionFailed,
@@ -198,8 +260,17 @@ impl CommandExecutor {
         Ok(OutputType::None)
     }
 
-    async fn delete_rss(&mut self, hash: &str) -> Result<OutputType, CommandError> {
-        if let Err(e) = self.core.lock().await.delete_rss(hash).await {
+    async fn delete_rss(&mut self, prefix: &str) -> Result<OutputType, CommandError> {
+        let hash = match self.resolve_podcast_hash(prefix).await {
+            Ok(h) => h,
+            Err(e) => {
+                return Err(Self::build_hash_resolution_error(
+                    format!("delete_rss {}", prefix),
+                    e,
+                ))
+            }
+        };
+        if let Err(e) = self.core.lock().await.delete_rss(&hash).await {
             return Err(CommandError::new(
                 Some(Box::new(e)),
                 command_error::ErrorKind::ExecutionFailed,
@@ -210,6 +281,50 @@ impl CommandExecutor {
         Ok(OutputType::None)
     }
 
+    async fn handle_refresh_command(
+        &mut self,
+        hash: Option<String>,
+    ) -> Result<OutputType, CommandError> {
+        let hashes = match self.core.lock().await.refresh(hash.as_deref()).await {
+            Ok(h) => h,
+            Err(e) => {
+                let message = format!("Refresh failed : {}", e);
+                return Err(CommandError::new(
+                    Some(Box::new(e)),
+                    CommandErrorKind::ExecutionFailed,
+                    Some("refresh".to_owned()),
+                    Some(message),
+                ));
+            }
+        };
+        self.update_autocompleter_hashes(hashes)
+            .await
+            .expect("Sending of new hashes to autocompleter failed");
+        Ok(OutputType::None)
+    }
+
+    async fn handle_load_archive_command(
+        &mut self,
+        hash: &str,
+    ) -> Result<OutputType, CommandError> {
+        let hashes = match BusinessCore::load_archive(self.core.clone(), hash).await {
+            Ok(h) => h,
+            Err(e) => {
+                let message = format!("Loading of archive failed : {}", e);
+                return Err(CommandError::new(
+                    Some(Box::new(e)),
+                    CommandErrorKind::ExecutionFailed,
+                    Some("load_archive".to_owned()),
+                    Some(message),
+                ));
+            }
+        };
+        self.update_autocompleter_hashes(hashes)
+            .await
+            .expect("Sending of new hashes to autocompleter failed");
+        Ok(OutputType::None)
+    }
+
     async fn advance_in_podcast(
         &mut self,
         duration: chrono::Duration,
@@ -261,7 +376,7 @@ impl CommandExecutor {
         let tmp_core = self.core.lock().await;
         let library = tmp_core.podcast_library.lock().await;
 
-        let all_episodes = library.podcasts.iter().flat_map(|p| &p.episodes);
+        let all_episodes = library.podcasts().iter().flat_map(|p| &p.episodes);
         let latest_episodes = all_episodes
             .filter(|e| e.was_published_recently())
             .cloned()
@@ -272,6 +387,302 @@ impl CommandExecutor {
         Ok(OutputType::Episodes(latest_episodes))
     }
 
+    async fn handle_search_command(&mut self, query: &str) -> Result<OutputType, CommandError> {
+        let tmp_core = self.core.lock().await;
+        let episodes = tmp_core.podcast_library.lock().await.search(query);
+        drop(tmp_core);
+
+        let hashes = episodes.iter().map(|e| e.hash()).collect();
+        self.update_autocompleter_hashes(hashes)
+            .await
+            .expect("Sending of new hashes to autocompleter failed");
+
+        Ok(OutputType::Episodes(episodes))
+    }
+
+    /// Displays the details of the episode matching the hash, or of the podcast if no episode does
+    async fn handle_see_command(&mut self, hash: &str) -> Result<OutputType, CommandError> {
+        let command = format!("see {}", hash);
+        match self.search_episode(hash).await {
+            Ok(episode) => {
+                let details = self.core.lock().await.get_episode_details(&episode).await;
+                return Ok(OutputType::EpisodeDetails(Box::new(details)));
+            }
+            Err(HashResolutionError::NotFound(_)) => (),
+            Err(e) => return Err(Self::build_hash_resolution_error(command, e)),
+        }
+
+        let podcast_hash = match self.resolve_podcast_hash(hash).await {
+            Ok(h) => h,
+            Err(e) => return Err(Self::build_hash_resolution_error(command, e)),
+        };
+        match self
+            .core
+            .lock()
+            .await
+            .get_podcast_details(&podcast_hash)
+            .await
+        {
+            Some(details) => Ok(OutputType::PodcastDetails(Box::new(details))),
+            None => Err(Self::build_hash_resolution_error(
+                command,
+                HashResolutionError::NotFound(hash.to_owned()),
+            )),
+        }
+    }
+
+    fn build_io_error(command: String, error: std::io::Error) -> CommandError {
+        let kind = match error.kind() {
+            std::io::ErrorKind::NotFound => CommandErrorKind::ObjectNotFound,
+            _ => CommandErrorKind::ExecutionFailed,
+        };
+        let message = error.to_string();
+        CommandError::new(Some(Box::new(error)), kind, Some(command), Some(message))
+    }
+
+    async fn handle_queue_add_command(&mut self, hash: &str) -> Result<OutputType, CommandError> {
+        let command = format!("queue_add {}", hash);
+        let episode = match self.search_episode(hash).await {
+            Ok(e) => e,
+            Err(e) => return Err(Self::build_hash_resolution_error(command, e)),
+        };
+        match self.core.lock().await.queue_episode(&episode).await {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error(command, e)),
+        }
+    }
+
+    async fn handle_queue_remove_command(
+        &mut self,
+        hash: &str,
+    ) -> Result<OutputType, CommandError> {
+        let command = format!("queue_remove {}", hash);
+        let episode = match self.search_episode(hash).await {
+            Ok(e) => e,
+            Err(e) => return Err(Self::build_hash_resolution_error(command, e)),
+        };
+        if let Err(e) = self.core.lock().await.unqueue_episode(&episode) {
+            return Err(Self::build_io_error(command, e));
+        }
+        self.handle_queue_command().await
+    }
+
+    async fn handle_queue_move_command(
+        &mut self,
+        hash: &str,
+        position: usize,
+    ) -> Result<OutputType, CommandError> {
+        let command = format!("queue_move {} {}", hash, position);
+        let episode = match self.search_episode(hash).await {
+            Ok(e) => e,
+            Err(e) => return Err(Self::build_hash_resolution_error(command, e)),
+        };
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .move_queued_episode(&episode, position - 1)
+        {
+            return Err(Self::build_io_error(command, e));
+        }
+        self.handle_queue_command().await
+    }
+
+    async fn handle_queue_clear_command(&mut self) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.clear_queue() {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error("queue_clear".to_owned(), e)),
+        }
+    }
+
+    async fn handle_queue_command(&mut self) -> Result<OutputType, CommandError> {
+        let episodes = self.core.lock().await.get_queued_episodes().await;
+        if episodes.is_empty() {
+            return Ok(OutputType::RawString("The queue is empty".to_owned()));
+        }
+
+        let hashes = episodes.iter().map(|e| e.hash()).collect();
+        self.update_autocompleter_hashes(hashes)
+            .await
+            .expect("Sending of new hashes to autocompleter failed");
+
+        Ok(OutputType::Episodes(episodes))
+    }
+
+    async fn handle_next_command(&mut self) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.play_next().await {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error("next".to_owned(), e)),
+        }
+    }
+
+    /// Selects the episodes of the playlist with the options it was saved with
+    async fn evaluate_playlist(
+        &self,
+        command: &str,
+        name: &str,
+    ) -> Result<Vec<PodcastEpisode>, CommandError> {
+        let tmp_core = self.core.lock().await;
+        let options = match tmp_core.get_playlist_definition(name) {
+            Some(o) => o,
+            None => {
+                return Err(CommandError::new(
+                    None,
+                    CommandErrorKind::ObjectNotFound,
+                    Some(command.to_owned()),
+                    Some(format!("Unknown playlist {}", name)),
+                ))
+            }
+        };
+        // Options are parsed again since the file may have been edited by hand
+        let filter = parse_episode_filter(command, &options)?;
+        let states = tmp_core.get_listening_states();
+        let episodes = filter.apply(&*tmp_core.podcast_library.lock().await, &states);
+        drop(tmp_core);
+        episodes.map_err(|e| Self::build_hash_resolution_error(command.to_owned(), e))
+    }
+
+    async fn handle_playlist_command(
+        &mut self,
+        name: Option<String>,
+    ) -> Result<OutputType, CommandError> {
+        let name = match name {
+            Some(n) => n,
+            None => {
+                let names = self.core.lock().await.get_playlist_names();
+                if names.is_empty() {
+                    return Ok(OutputType::RawString("No playlist saved".to_owned()));
+                }
+                return Ok(OutputType::RawString(format!(
+                    "Playlists : {}",
+                    names.join(", ")
+                )));
+            }
+        };
+        let episodes = self
+            .evaluate_playlist(&format!("playlist {}", name), &name)
+            .await?;
+        if episodes.is_empty() {
+            return Ok(OutputType::RawString(
+                "No episode matches the playlist".to_owned(),
+            ));
+        }
+
+        let hashes = episodes.iter().map(|e| e.hash()).collect();
+        self.update_autocompleter_hashes(hashes)
+            .await
+            .expect("Sending of new hashes to autocompleter failed");
+
+        Ok(OutputType::Episodes(episodes))
+    }
+
+    async fn handle_play_playlist_command(
+        &mut self,
+        name: &str,
+    ) -> Result<OutputType, CommandError> {
+        let command = format!("play_playlist {}", name);
+        let episodes = self.evaluate_playlist(&command, name).await?;
+        if episodes.is_empty() {
+            return Ok(OutputType::RawString(
+                "No episode matches the playlist".to_owned(),
+            ));
+        }
+        match self.core.lock().await.play_episodes(&episodes).await {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error(command, e)),
+        }
+    }
+
+    /// Replaces the hashes of the podcasts given in the options of a playlist with the URLs of their feeds,
+    /// since the hash of a podcast changes along with its title or description
+    fn resolve_playlist_podcasts(
+        library: &PodcastLibrary,
+        options: Vec<String>,
+    ) -> Result<Vec<String>, HashResolutionError> {
+        let mut resolved_options = vec![];
+        let mut options = options.into_iter();
+        while let Some(option) = options.next() {
+            let podcast = match option.as_str() {
+                "--podcast" => options.next(),
+                "--since" | "--sort" | "--limit" => {
+                    resolved_options.push(option);
+                    resolved_options.extend(options.next());
+                    continue;
+                }
+                o if o.starts_with("--") => {
+                    resolved_options.push(option);
+                    continue;
+                }
+                // The hash of a podcast can also be given without option
+                _ => Some(option),
+            };
+            let podcast = match podcast {
+                Some(p) if Url::parse(&p).is_ok() => p,
+                Some(p) => {
+                    let hash = library.resolve_podcast_hash(&p)?;
+                    match library.search_podcast(&hash) {
+                        Some(p) if !p.feed_url.is_empty() => p.feed_url,
+                        _ => hash,
+                    }
+                }
+                None => continue,
+            };
+            resolved_options.push("--podcast".to_owned());
+            resolved_options.push(podcast);
+        }
+        Ok(resolved_options)
+    }
+
+    async fn handle_save_playlist_command(
+        &mut self,
+        name: &str,
+        options: Vec<String>,
+    ) -> Result<OutputType, CommandError> {
+        let command = format!("save_playlist {}", name);
+        let mut tmp_core = self.core.lock().await;
+        let options =
+            Self::resolve_playlist_podcasts(&*tmp_core.podcast_library.lock().await, options)
+                .map_err(|e| Self::build_hash_resolution_error(command.clone(), e))?;
+        match tmp_core.save_playlist(name, options) {
+            Ok(_) => Ok(OutputType::RawString(format!("Playlist {} saved", name))),
+            Err(e) => Err(Self::build_io_error(command, e)),
+        }
+    }
+
+    async fn handle_delete_playlist_command(
+        &mut self,
+        name: &str,
+    ) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.delete_playlist(name) {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error(format!("delete_playlist {}", name), e)),
+        }
+    }
+
+    async fn handle_chapters_command(&mut self) -> Result<OutputType, CommandError> {
+        let chapters = self.core.lock().await.get_chapters().await;
+        if chapters.is_empty() {
+            return Ok(OutputType::RawString(
+                "The selected episode has no chapters".to_owned(),
+            ));
+        }
+        Ok(OutputType::Chapters(chapters))
+    }
+
+    async fn handle_next_chapter_command(&mut self) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.next_chapter().await {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error("next_chapter".to_owned(), e)),
+        }
+    }
+
+    async fn handle_previous_chapter_command(&mut self) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.previous_chapter().await {
+            Ok(_) => Ok(OutputType::None),
+            Err(e) => Err(Self::build_io_error("previous_chapter".to_owned(), e)),
+        }
+    }
+
     fn handle_help_command(&mut self, command: Option<String>) -> Result<OutputType, CommandError> {
         let helps = match command {
             Some(c) => match self.command_help_library.get_description(&c) {
@@ -328,6 +739,150 @@ impl CommandExecutor {
         Ok(OutputType::None)
     }
 
+    async fn handle_import_opml_command(&mut self, path: &str) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.import_opml(Path::new(path)).await {
+            Ok(report) => Ok(OutputType::RawString(report.to_string())),
+            Err(e) => Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("import_opml".to_owned()),
+                Some("OPML import failed".to_owned()),
+            )),
+        }
+    }
+
+    async fn handle_export_opml_command(&mut self, path: &str) -> Result<OutputType, CommandError> {
+        match self.core.lock().await.export_opml(Path::new(path)).await {
+            Ok(count) => Ok(OutputType::RawString(format!(
+                "Exported {} subscription(s) to {}",
+                count, path
+            ))),
+            Err(e) => Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("export_opml".to_owned()),
+                Some("OPML export failed".to_owned()),
+            )),
+        }
+    }
+
+    async fn handle_check_feeds_command(&mut self) -> Result<OutputType, CommandError> {
+        let diagnostics = self.core.lock().await.check_feeds().await;
+        Ok(OutputType::FeedDiagnostics(diagnostics))
+    }
+
+    async fn handle_set_feed_auth_command(
+        &mut self,
+        url: &Url,
+        username: &str,
+        password: &str,
+    ) -> Result<OutputType, CommandError> {
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .set_feed_basic_auth(url.as_str(), username, password)
+            .await
+        {
+            return Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("set_feed_auth".to_owned()),
+                Some("Storing of credentials failed".to_owned()),
+            ));
+        }
+        Ok(OutputType::None)
+    }
+
+    async fn handle_set_feed_header_command(
+        &mut self,
+        url: &Url,
+        name: &str,
+        value: &str,
+    ) -> Result<OutputType, CommandError> {
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .set_feed_header(url.as_str(), name, value)
+            .await
+        {
+            return Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("set_feed_header".to_owned()),
+                Some("Storing of header failed".to_owned()),
+            ));
+        }
+        Ok(OutputType::None)
+    }
+
+    async fn handle_clear_feed_auth_command(
+        &mut self,
+        url: &Url,
+    ) -> Result<OutputType, CommandError> {
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .clear_feed_credentials(url.as_str())
+            .await
+        {
+            return Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("clear_feed_auth".to_owned()),
+                Some("Deletion of credentials failed".to_owned()),
+            ));
+        }
+        Ok(OutputType::None)
+    }
+
+    async fn handle_set_alias_command(
+        &mut self,
+        url: &Url,
+        alias: Option<&str>,
+    ) -> Result<OutputType, CommandError> {
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .set_podcast_alias(url.as_str(), alias)
+            .await
+        {
+            return Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some("set_alias".to_owned()),
+                Some("Storing of alias failed".to_owned()),
+            ));
+        }
+        Ok(OutputType::None)
+    }
+
+    async fn handle_set_paused_command(
+        &mut self,
+        url: &Url,
+        paused: bool,
+    ) -> Result<OutputType, CommandError> {
+        if let Err(e) = self
+            .core
+            .lock()
+            .await
+            .set_podcast_paused(url.as_str(), paused)
+            .await
+        {
+            let command_name = if paused { "pause_feed" } else { "resume_feed" };
+            return Err(CommandError::new(
+                Some(Box::new(e)),
+                CommandErrorKind::ExecutionFailed,
+                Some(command_name.to_owned()),
+                Some("Update of subscription failed".to_owned()),
+            ));
+        }
+        Ok(OutputType::None)
+    }
+
     pub async fn execute_command(&mut self, command: Command) -> Result<OutputType, CommandError> {
         let command_output = match command {
             Command::Pause => self.handle_pause(command).await?,
@@ -339,13 +894,18 @@ impl CommandExecutor {
             Command::Exit => OutputType::None,
             Command::Help(command) => self.handle_help_command(command)?,
             Command::ListPodcasts => self.handle_list_podcasts(command).await?,
-            Command::ListEpisodes(ref hash) => {
-                let hash = hash.clone();
-                self.handle_list_episodes(command, hash).await?
+            Command::ListEpisodes(ref filter) => {
+                let filter = filter.clone();
+                self.handle_list_episodes(command, filter).await?
             }
+            Command::Search(query) => self.handle_search_command(&query).await?,
             Command::Select(hash) => self.select_episode(&hash).await?,
+            Command::See(hash) => self.handle_see_command(&hash).await?,
             Command::AddRss(url) => self.add_rss(&url.0).await?,
+            Command::AddFolder(path) => self.handle_add_folder_command(&path).await?,
             Command::DeleteRss(hash) => self.delete_rss(&hash).await?,
+            Command::Refresh(hash) => self.handle_refresh_command(hash).await?,
+            Command::LoadArchive(hash) => self.handle_load_archive_command(&hash).await?,
             Command::Advance(duration) => self.advance_in_podcast(duration.0).await?,
             Command::GoBack(duration) => self.go_back_in_podcast(duration.0).await?,
             Command::MarkAsFinished => self.handle_mark_as_finished_command().await?,
@@ -355,14 +915,41 @@ impl CommandExecutor {
                 self.handle_volume_offset_command(-(offset as i32)).await?
             }
             Command::SetVolume(new_volume) => self.handle_set_volume_command(new_volume).await?,
-            _ => {
-                return Err(CommandError::new(
-                    None,
-                    CommandErrorKind::UnhandledCommand,
-                    None,
-                    Some(format!("Command {:#?} unhandled by executor", command)),
-                ))
+            Command::ImportOpml(path) => self.handle_import_opml_command(&path).await?,
+            Command::ExportOpml(path) => self.handle_export_opml_command(&path).await?,
+            Command::CheckFeeds => self.handle_check_feeds_command().await?,
+            Command::SetFeedAuth(url, username, password) => {
+                self.handle_set_feed_auth_command(&url.0, &username, &password)
+                    .await?
+            }
+            Command::SetFeedHeader(url, name, value) => {
+                self.handle_set_feed_header_command(&url.0, &name, &value)
+                    .await?
+            }
+            Command::ClearFeedAuth(url) => self.handle_clear_feed_auth_command(&url.0).await?,
+            Command::SetAlias(url, alias) => {
+                self.handle_set_alias_command(&url.0, alias.as_deref())
+                    .await?
+            }
+            Command::PauseFeed(url) => self.handle_set_paused_command(&url.0, true).await?,
+            Command::ResumeFeed(url) => self.handle_set_paused_command(&url.0, false).await?,
+            Command::QueueAdd(hash) => self.handle_queue_add_command(&hash).await?,
+            Command::QueueRemove(hash) => self.handle_queue_remove_command(&hash).await?,
+            Command::QueueMove(hash, position) => {
+                self.handle_queue_move_command(&hash, position).await?
+            }
+            Command::QueueClear => self.handle_queue_clear_command().await?,
+            Command::Queue => self.handle_queue_command().await?,
+            Command::Next => self.handle_next_command().await?,
+            Command::Playlist(name) => self.handle_playlist_command(name).await?,
+            Command::PlayPlaylist(name) => self.handle_play_playlist_command(&name).await?,
+            Command::SavePlaylist(name, options) => {
+                self.handle_save_playlist_command(&name, options).await?
             }
+            Command::DeletePlaylist(name) => self.handle_delete_playlist_command(&name).await?,
+            Command::Chapters => self.handle_chapters_command().await?,
+            Command::NextChapter => self.handle_next_chapter_command().await?,
+            Command::PreviousChapter => self.handle_previous_chapter_command().await?,
         };
 
         Ok(command_output)
@@ -375,13 +962,16 @@ mod tests {
     use super::*;
     use crate::mocks::mp3_player::MockMp3Player;
 
-    use path_providing::dummy_path_provider::DummyPathProvider;
+    use path_providing::dummy_path_provider::{DummyPathProvider, PathProvider};
+    use podcast_player::duration_wrapper::DurationWrapper;
+    use podcast_player::enums::player_state::Mp3PlayerState;
     use podcast_player::player_error::{ErrorKind, PlayerError};
     use podcast_player::players::mp3_player::Mp3Player as TraitMp3Player;
+    use podcast_player::traits::PlayerObserver;
 
     use std::sync::Arc;
     use test_case::test_case;
-    use tokio::sync::Mutex as TokioMutex;
+    use tokio::sync::{Mutex as TokioMutex, RwLock};
 
     fn instanciate_mock_mp3_player() -> Arc<TokioMutex<MockMp3Player>> {
         Arc::new(TokioMutex::new(MockMp3Player::new()))
@@ -486,4 +1076,143 @@ mod tests {
 
         Ok(())
     }
+
+    fn build_episode(guid: &str) -> PodcastEpisode {
+        let mut episode = PodcastEpisode {
+            title: guid.to_owned(),
+            ..Default::default()
+        };
+        episode.guid.value = guid.to_owned();
+        episode
+    }
+
+    /// Builds a core whose library holds the episodes, the player playing the first one until its end
+    async fn instanciate_core_playing(
+        test_name: &str,
+        episodes: &[PodcastEpisode],
+    ) -> (Arc<TokioMutex<BusinessCore>>, Arc<DummyPathProvider>) {
+        let root_path = std::env::temp_dir().join(test_name);
+        let _ = std::fs::remove_dir_all(&root_path);
+        let path_provider = Arc::new(DummyPathProvider::new(root_path.to_str().unwrap()));
+
+        let mp3_player = instanciate_mock_mp3_player();
+        let mut player = mp3_player.lock().await;
+        let selected_episode = Arc::new(std::sync::Mutex::new(episodes.first().cloned()));
+        let selected_episode_cloned = selected_episode.clone();
+        player.expect_get_selected_episode().returning(move || {
+            let episode = selected_episode_cloned.lock().unwrap().clone();
+            episode.map(|e| Arc::new(RwLock::new(e)))
+        });
+        player
+            .expect_set_selected_episode()
+            .returning(move |episode| {
+                *selected_episode.lock().unwrap() = episode;
+                Ok(())
+            });
+        let path_provider_cloned = path_provider.clone();
+        player
+            .expect_compute_episode_path()
+            .returning(move |episode| path_provider_cloned.compute_episode_path(episode));
+        player
+            .expect_get_selected_episode_progression()
+            .returning(|| Some(DurationWrapper::new(std::time::Duration::from_secs(3600))));
+        player
+            .expect_get_state()
+            .returning(|| Mp3PlayerState::Stopped);
+        player.expect_reset_progression().return_const(());
+        player.expect_play().return_const(());
+        player.expect_register_observer().return_const(());
+        drop(player);
+
+        let core = BusinessCore::new_in_arc(mp3_player, path_provider.clone(), None).await;
+        let mut podcast = Podcast::new("Podcast", "", "", None, None, None, episodes.to_vec());
+        podcast.set_feed_url("https://www.example.com/feed.xml");
+        let locked_core = core.lock().await;
+        locked_core.initialize();
+        locked_core.podcast_library.lock().await.push(vec![podcast]);
+        drop(locked_core);
+        (core, path_provider)
+    }
+
+    #[tokio::test]
+    pub async fn test_queue_advance_after_finished_episode() {
+        let finished_episode = build_episode("finished");
+        let next_episode = build_episode("next");
+        let (core, path_provider) = instanciate_core_playing(
+            "test_queue_advance_after_finished_episode",
+            &[finished_episode.clone(), next_episode.clone()],
+        )
+        .await;
+        std::fs::write(path_provider.compute_episode_path(&next_episode), b"").unwrap();
+        let mut core = core.lock().await;
+        core.queue_episode(&next_episode).await.unwrap();
+
+        core.on_podcast_finished(&finished_episode.persistent_key())
+            .await;
+
+        // Resuming the finished episode must start it over
+        assert!(!path_provider
+            .podcast_progress_file_path(&finished_episode.persistent_key())
+            .exists());
+        assert!(core.get_queued_episodes().await.is_empty());
+        std::fs::remove_dir_all(path_provider.app_dir_path()).unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_queue_keeps_episode_failing_to_play() {
+        let finished_episode = build_episode("finished");
+        let missing_episode = build_episode("missing");
+        let (core, path_provider) = instanciate_core_playing(
+            "test_queue_keeps_episode_failing_to_play",
+            &[finished_episode, missing_episode.clone()],
+        )
+        .await;
+        let mut core = core.lock().await;
+        core.queue_episode(&missing_episode).await.unwrap();
+
+        // The episode has no URL to be downloaded from
+        assert!(core.play_next().await.is_err());
+        let queued_keys = core
+            .get_queued_episodes()
+            .await
+            .iter()
+            .map(|e| e.persistent_key())
+            .collect::<Vec<String>>();
+        assert_eq!(queued_keys, vec![missing_episode.persistent_key()]);
+        std::fs::remove_dir_all(path_provider.app_dir_path()).unwrap();
+    }
+
+    #[test]
+    pub fn test_resolve_playlist_podcasts() {
+        let mut podcast = Podcast::new("News", "", "", None, None, None, vec![]);
+        podcast.set_feed_url("https://www.example.com/news.xml");
+        let hash = podcast.hash();
+        let mut library = PodcastLibrary::new();
+        library.push(vec![podcast]);
+
+        let to_options = |options: &[&str]| {
+            options
+                .iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<String>>()
+        };
+        let options = to_options(&["--since", "3d", "--podcast", &hash[..4], "--unfinished"]);
+        assert_eq!(
+            CommandExecutor::resolve_playlist_podcasts(&library, options).unwrap(),
+            to_options(&[
+                "--since",
+                "3d",
+                "--podcast",
+                "https://www.example.com/news.xml",
+                "--unfinished"
+            ])
+        );
+        assert_eq!(
+            CommandExecutor::resolve_playlist_podcasts(&library, to_options(&[&hash])).unwrap(),
+            to_options(&["--podcast", "https://www.example.com/news.xml"])
+        );
+        assert!(
+            CommandExecutor::resolve_playlist_podcasts(&library, to_options(&["abcdef"])).is_err()
+        );
+    }
 }
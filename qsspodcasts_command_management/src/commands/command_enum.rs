@@ -5,6 +5,8 @@ use url as extern_url;
 
 use strum_macros::{Display, EnumIter};
 
+pub use podcast_management::episode_filter::EpisodeFilter;
+
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub struct CommandUrl(pub extern_url::Url);
 
@@ -32,11 +34,14 @@ pub enum Command {
     Exit,
     Help(Option<String>),
     ListPodcasts,
-    ListEpisodes(Option<String>),
-    Search,
-    See,
+    ListEpisodes(EpisodeFilter),
+    Search(String),
+    See(String),
     AddRss(CommandUrl),
+    AddFolder(String),
     DeleteRss(String),
+    LoadArchive(String),
+    Refresh(Option<String>),
     Select(String),
     Advance(CommandDuration),
     GoBack(CommandDuration),
@@ -45,4 +50,30 @@ pub enum Command {
     VolumeUp(u32),
     VolumeDown(u32),
     SetVolume(u32),
+    ImportOpml(String),
+    ExportOpml(String),
+    CheckFeeds,
+    SetFeedAuth(CommandUrl, String, String),
+    SetFeedHeader(CommandUrl, String, String),
+    ClearFeedAuth(CommandUrl),
+    /// URL of the feed, and the name displayed instead of its title. The alias is removed if there is none
+    SetAlias(CommandUrl, Option<String>),
+    PauseFeed(CommandUrl),
+    ResumeFeed(CommandUrl),
+    QueueAdd(String),
+    QueueRemove(String),
+    /// Hash of the episode, and its new position in the queue, starting at 1
+    QueueMove(String, usize),
+    QueueClear,
+    Queue,
+    Next,
+    /// Name of the playlist, all playlists being listed if none is given
+    Playlist(Option<String>),
+    PlayPlaylist(String),
+    /// Name of the playlist, and the options of the filter selecting its episodes
+    SavePlaylist(String, Vec<String>),
+    DeletePlaylist(String),
+    Chapters,
+    NextChapter,
+    PreviousChapter,
 }
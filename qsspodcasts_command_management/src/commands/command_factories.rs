@@ -1,7 +1,8 @@
 use super::command_enum::{Command, CommandDuration, CommandUrl};
 use super::duration_utils::string_to_duration;
-use super::hash_utils::is_hash;
+use super::hash_utils::{is_hash, is_hash_prefix};
 use crate::command_error::{CommandError, ErrorKind};
+use podcast_management::episode_filter::{EpisodeFilter, EpisodeSortKey, ListeningStatus};
 use std::collections::HashMap;
 use url::Url;
 
@@ -28,10 +29,10 @@ fn build_parsing_failed_error(command_name: &str, error_text: &str) -> CommandEr
 }
 
 pub fn build_play_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
-    let command = if !parameters.is_empty() && is_hash(&parameters[0]) {
+    let command = if !parameters.is_empty() && is_hash_prefix(&parameters[0]) {
         let hash = parameters.pop().unwrap();
         Command::Play(Some(hash))
-    } else if !parameters.is_empty() && !is_hash(&parameters[0]) {
+    } else if !parameters.is_empty() && !is_hash_prefix(&parameters[0]) {
         let error = build_parsing_failed_error("Play", "Parsing of hash failed");
         return Err(error);
     } else {
@@ -52,17 +53,94 @@ pub fn build_list_podcasts_command(_parameters: Vec<String>) -> Result<Command,
     Ok(Command::ListPodcasts)
 }
 
-pub fn build_list_episodes_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
-    let command = if !parameters.is_empty() && is_hash(&parameters[0]) {
-        let hash = parameters.pop().unwrap();
-        Command::ListEpisodes(Some(hash))
-    } else if !parameters.is_empty() && !is_hash(&parameters[0]) {
-        let error = build_parsing_failed_error("Play", "Parsing of hash failed");
-        return Err(error);
-    } else {
-        Command::ListEpisodes(None)
-    };
-    Ok(command)
+/// Parses a maximum age, either a number of days (e.g. : 7d) or weeks (e.g. : 2w), or a duration (e.g. : 12h)
+fn parse_age(age: &str) -> Option<chrono::Duration> {
+    if let Some(days) = age.strip_suffix('d') {
+        return days.parse::<i64>().ok().map(chrono::Duration::days);
+    }
+    if let Some(weeks) = age.strip_suffix('w') {
+        return weeks.parse::<i64>().ok().map(chrono::Duration::weeks);
+    }
+    string_to_duration(age).ok()
+}
+
+/// Parses options selecting and ordering episodes, e.g. : `--unfinished --since 7d --podcast a1b2c3 --sort duration --limit 20`.
+///
+/// `--podcast` takes the hash of a podcast or the URL of its feed, and can be given several times to select episodes
+/// of several podcasts. A hash given without option is the one of the podcast, like in former versions of
+/// list_episodes.
+pub fn parse_episode_filter(
+    command_name: &str,
+    parameters: &[String],
+) -> Result<EpisodeFilter, CommandError> {
+    let mut filter = EpisodeFilter::default();
+    let mut parameters = parameters.iter();
+    while let Some(parameter) = parameters.next() {
+        let mut read_value = |option: &str| {
+            parameters.next().ok_or_else(|| {
+                build_parsing_failed_error(command_name, &format!("Missing value of {}", option))
+            })
+        };
+        match parameter.as_str() {
+            "--finished" => filter.status = Some(ListeningStatus::Finished),
+            "--unfinished" => filter.status = Some(ListeningStatus::Unfinished),
+            "--in_progress" => filter.status = Some(ListeningStatus::InProgress),
+            "--reverse" => filter.reverse = true,
+            "--podcast" => {
+                let podcast = read_value(parameter)?;
+                if !is_hash_prefix(podcast) && Url::parse(podcast).is_err() {
+                    return Err(build_parsing_failed_error(
+                        command_name,
+                        "Parsing of hash failed",
+                    ));
+                }
+                filter.podcasts.push(podcast.clone());
+            }
+            "--since" => {
+                let age = read_value(parameter)?;
+                filter.since = Some(parse_age(age).ok_or_else(|| {
+                    build_parsing_failed_error(command_name, &format!("Invalid age {}", age))
+                })?);
+            }
+            "--sort" => {
+                filter.sort_key = match read_value(parameter)?.as_str() {
+                    "date" => EpisodeSortKey::Date,
+                    "duration" => EpisodeSortKey::Duration,
+                    "title" => EpisodeSortKey::Title,
+                    "podcast" => EpisodeSortKey::Podcast,
+                    k => {
+                        return Err(build_parsing_failed_error(
+                            command_name,
+                            &format!("Unknown sort key {}", k),
+                        ))
+                    }
+                }
+            }
+            "--limit" => {
+                let limit = read_value(parameter)?;
+                filter.limit = Some(limit.parse::<usize>().map_err(|_| {
+                    build_parsing_failed_error(command_name, &format!("Invalid limit {}", limit))
+                })?);
+            }
+            p if filter.podcasts.is_empty() && is_hash_prefix(p) => {
+                filter.podcasts.push(p.to_owned())
+            }
+            p => {
+                return Err(build_parsing_failed_error(
+                    command_name,
+                    &format!("Unknown option {}", p),
+                ))
+            }
+        }
+    }
+    Ok(filter)
+}
+
+pub fn build_list_episodes_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::ListEpisodes(parse_episode_filter(
+        "list_episodes",
+        &parameters,
+    )?))
 }
 
 pub fn build_select_command(parameters: Vec<String>) -> Result<Command, CommandError> {
@@ -71,7 +149,7 @@ pub fn build_select_command(parameters: Vec<String>) -> Result<Command, CommandE
     }
 
     let hash = &parameters[0];
-    if hash.len() != HASH_LEN || !is_hash(hash) {
+    if !is_hash_prefix(hash) {
         return Err(build_parsing_failed_error(
             "select",
             "Parameter parsing failed",
@@ -80,6 +158,21 @@ pub fn build_select_command(parameters: Vec<String>) -> Result<Command, CommandE
     Ok(Command::Select(hash.to_string()))
 }
 
+pub fn build_see_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("see"));
+    }
+
+    let hash = &parameters[0];
+    if !is_hash_prefix(hash) {
+        return Err(build_parsing_failed_error(
+            "see",
+            "Parameter parsing failed",
+        ));
+    }
+    Ok(Command::See(hash.to_string()))
+}
+
 pub fn build_add_rss_command(parameters: Vec<String>) -> Result<Command, CommandError> {
     if parameters.len() != 1 {
         return Err(build_bad_parameter_count_error("add_rss"));
@@ -96,13 +189,45 @@ pub fn build_add_rss_command(parameters: Vec<String>) -> Result<Command, Command
     Ok(Command::AddRss(CommandUrl(url.unwrap())))
 }
 
+pub fn build_load_archive_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("load_archive"));
+    }
+
+    let hash = &parameters[0];
+    if hash.len() != HASH_LEN || !is_hash(hash) {
+        return Err(build_parsing_failed_error(
+            "load_archive",
+            "Parameter parsing failed",
+        ));
+    }
+    Ok(Command::LoadArchive(hash.to_string()))
+}
+
+pub fn build_refresh_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    match parameters.len() {
+        0 => Ok(Command::Refresh(None)),
+        1 => {
+            let hash = &parameters[0];
+            if hash.len() != HASH_LEN || !is_hash(hash) {
+                return Err(build_parsing_failed_error(
+                    "refresh",
+                    "Parameter parsing failed",
+                ));
+            }
+            Ok(Command::Refresh(Some(hash.to_string())))
+        }
+        _ => Err(build_bad_parameter_count_error("refresh")),
+    }
+}
+
 pub fn build_delete_rss_command(parameters: Vec<String>) -> Result<Command, CommandError> {
     if parameters.len() != 1 {
         return Err(build_bad_parameter_count_error("delete_rss"));
     }
 
     let hash = &parameters[0];
-    if hash.len() != HASH_LEN || !is_hash(hash) {
+    if !is_hash_prefix(hash) {
         return Err(build_parsing_failed_error(
             "delete_rss",
             "Parameter parsing failed",
@@ -189,6 +314,217 @@ pub fn build_set_volume_command(parameters: Vec<String>) -> Result<Command, Comm
     Ok(Command::SetVolume(volume_offset.unwrap()))
 }
 
+pub fn build_import_opml_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("import_opml"));
+    }
+    Ok(Command::ImportOpml(parameters[0].clone()))
+}
+
+pub fn build_add_folder_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.is_empty() {
+        return Err(build_bad_parameter_count_error("add_folder"));
+    }
+    // Folder names often contain spaces
+    Ok(Command::AddFolder(parameters.join(" ")))
+}
+
+pub fn build_search_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.is_empty() {
+        return Err(build_bad_parameter_count_error("search"));
+    }
+    // Quotes are kept, they delimit the phrases of the query
+    Ok(Command::Search(parameters.join(" ")))
+}
+
+pub fn build_export_opml_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("export_opml"));
+    }
+    Ok(Command::ExportOpml(parameters[0].clone()))
+}
+
+pub fn build_check_feeds_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::CheckFeeds)
+}
+
+fn parse_queued_episode_hash(command_name: &str, hash: &str) -> Result<String, CommandError> {
+    if !is_hash_prefix(hash) {
+        return Err(build_parsing_failed_error(
+            command_name,
+            "Parameter parsing failed",
+        ));
+    }
+    Ok(hash.to_owned())
+}
+
+pub fn build_queue_add_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("queue_add"));
+    }
+    Ok(Command::QueueAdd(parse_queued_episode_hash(
+        "queue_add",
+        &parameters[0],
+    )?))
+}
+
+pub fn build_queue_remove_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("queue_remove"));
+    }
+    Ok(Command::QueueRemove(parse_queued_episode_hash(
+        "queue_remove",
+        &parameters[0],
+    )?))
+}
+
+pub fn build_queue_move_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 2 {
+        return Err(build_bad_parameter_count_error("queue_move"));
+    }
+    let hash = parse_queued_episode_hash("queue_move", &parameters[0])?;
+    match parameters[1].parse::<usize>() {
+        Ok(position) if position != 0 => Ok(Command::QueueMove(hash, position)),
+        _ => Err(build_parsing_failed_error(
+            "queue_move",
+            "Position must be a number greater than 0",
+        )),
+    }
+}
+
+pub fn build_queue_clear_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::QueueClear)
+}
+
+pub fn build_queue_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::Queue)
+}
+
+pub fn build_next_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::Next)
+}
+
+pub fn build_playlist_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() > 1 {
+        return Err(build_bad_parameter_count_error("playlist"));
+    }
+    Ok(Command::Playlist(parameters.pop()))
+}
+
+pub fn build_play_playlist_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("play_playlist"));
+    }
+    Ok(Command::PlayPlaylist(parameters.pop().unwrap()))
+}
+
+/// The options are checked here so that a saved playlist can always be evaluated
+pub fn build_save_playlist_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.is_empty() {
+        return Err(build_bad_parameter_count_error("save_playlist"));
+    }
+    let name = parameters.remove(0);
+    if name.starts_with("--") {
+        return Err(build_parsing_failed_error(
+            "save_playlist",
+            "The name of the playlist must come before its options",
+        ));
+    }
+    parse_episode_filter("save_playlist", &parameters)?;
+    Ok(Command::SavePlaylist(name, parameters))
+}
+
+pub fn build_delete_playlist_command(mut parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("delete_playlist"));
+    }
+    Ok(Command::DeletePlaylist(parameters.pop().unwrap()))
+}
+
+pub fn build_chapters_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::Chapters)
+}
+
+pub fn build_next_chapter_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::NextChapter)
+}
+
+pub fn build_previous_chapter_command(_parameters: Vec<String>) -> Result<Command, CommandError> {
+    Ok(Command::PreviousChapter)
+}
+
+fn parse_command_url(command_name: &str, url: &str) -> Result<CommandUrl, CommandError> {
+    match Url::parse(url) {
+        Ok(u) => Ok(CommandUrl(u)),
+        Err(_) => Err(build_parsing_failed_error(
+            command_name,
+            &format!("Parsing of URL {} failed", url),
+        )),
+    }
+}
+
+pub fn build_set_feed_auth_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 3 {
+        return Err(build_bad_parameter_count_error("set_feed_auth"));
+    }
+    let url = parse_command_url("set_feed_auth", &parameters[0])?;
+    Ok(Command::SetFeedAuth(
+        url,
+        parameters[1].clone(),
+        parameters[2].clone(),
+    ))
+}
+
+pub fn build_set_feed_header_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() < 3 {
+        return Err(build_bad_parameter_count_error("set_feed_header"));
+    }
+    let url = parse_command_url("set_feed_header", &parameters[0])?;
+    // Header values such as "Bearer <TOKEN>" may contain spaces
+    Ok(Command::SetFeedHeader(
+        url,
+        parameters[1].clone(),
+        parameters[2..].join(" "),
+    ))
+}
+
+pub fn build_clear_feed_auth_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("clear_feed_auth"));
+    }
+    let url = parse_command_url("clear_feed_auth", &parameters[0])?;
+    Ok(Command::ClearFeedAuth(url))
+}
+
+pub fn build_set_alias_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.is_empty() {
+        return Err(build_bad_parameter_count_error("set_alias"));
+    }
+    let url = parse_command_url("set_alias", &parameters[0])?;
+    // Aliases may contain spaces
+    let alias = match parameters.len() {
+        1 => None,
+        _ => Some(parameters[1..].join(" ")),
+    };
+    Ok(Command::SetAlias(url, alias))
+}
+
+pub fn build_pause_feed_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("pause_feed"));
+    }
+    let url = parse_command_url("pause_feed", &parameters[0])?;
+    Ok(Command::PauseFeed(url))
+}
+
+pub fn build_resume_feed_command(parameters: Vec<String>) -> Result<Command, CommandError> {
+    if parameters.len() != 1 {
+        return Err(build_bad_parameter_count_error("resume_feed"));
+    }
+    let url = parse_command_url("resume_feed", &parameters[0])?;
+    Ok(Command::ResumeFeed(url))
+}
+
 pub fn get_factory_hashmap() -> HashMap<String, FactoryFn> {
     let mut factory_hashmap: HashMap<String, FactoryFn> = HashMap::new();
     factory_hashmap.insert(Command::Play(None).to_string(), build_play_command);
@@ -199,21 +535,34 @@ pub fn get_factory_hashmap() -> HashMap<String, FactoryFn> {
         build_list_podcasts_command,
     );
     factory_hashmap.insert(
-        Command::ListEpisodes(None).to_string(),
+        Command::ListEpisodes(EpisodeFilter::default()).to_string(),
         build_list_episodes_command,
     );
     factory_hashmap.insert(
         Command::Select("".to_string()).to_string(),
         build_select_command,
     );
+    factory_hashmap.insert(
+        Command::See(String::default()).to_string(),
+        build_see_command,
+    );
     factory_hashmap.insert(
         Command::AddRss(CommandUrl(Url::parse("https://www.google.com").unwrap())).to_string(),
         build_add_rss_command,
     );
+    factory_hashmap.insert(
+        Command::AddFolder(String::default()).to_string(),
+        build_add_folder_command,
+    );
     factory_hashmap.insert(
         Command::DeleteRss("".to_string()).to_string(),
         build_delete_rss_command,
     );
+    factory_hashmap.insert(
+        Command::LoadArchive(String::default()).to_string(),
+        build_load_archive_command,
+    );
+    factory_hashmap.insert(Command::Refresh(None).to_string(), build_refresh_command);
     factory_hashmap.insert(
         Command::Advance(CommandDuration(chrono::Duration::seconds(0))).to_string(),
         build_advance_command,
@@ -237,6 +586,79 @@ pub fn get_factory_hashmap() -> HashMap<String, FactoryFn> {
         build_volume_down_command,
     );
     factory_hashmap.insert(Command::SetVolume(0).to_string(), build_set_volume_command);
+    factory_hashmap.insert(
+        Command::ImportOpml(String::default()).to_string(),
+        build_import_opml_command,
+    );
+    factory_hashmap.insert(
+        Command::ExportOpml(String::default()).to_string(),
+        build_export_opml_command,
+    );
+    factory_hashmap.insert(Command::CheckFeeds.to_string(), build_check_feeds_command);
+    factory_hashmap.insert(
+        Command::Search(String::default()).to_string(),
+        build_search_command,
+    );
+    factory_hashmap.insert(
+        Command::SetFeedAuth(CommandUrl::default(), String::default(), String::default())
+            .to_string(),
+        build_set_feed_auth_command,
+    );
+    factory_hashmap.insert(
+        Command::SetFeedHeader(CommandUrl::default(), String::default(), String::default())
+            .to_string(),
+        build_set_feed_header_command,
+    );
+    factory_hashmap.insert(
+        Command::ClearFeedAuth(CommandUrl::default()).to_string(),
+        build_clear_feed_auth_command,
+    );
+    factory_hashmap.insert(
+        Command::SetAlias(CommandUrl::default(), None).to_string(),
+        build_set_alias_command,
+    );
+    factory_hashmap.insert(
+        Command::PauseFeed(CommandUrl::default()).to_string(),
+        build_pause_feed_command,
+    );
+    factory_hashmap.insert(
+        Command::ResumeFeed(CommandUrl::default()).to_string(),
+        build_resume_feed_command,
+    );
+    factory_hashmap.insert(
+        Command::QueueAdd(String::default()).to_string(),
+        build_queue_add_command,
+    );
+    factory_hashmap.insert(
+        Command::QueueRemove(String::default()).to_string(),
+        build_queue_remove_command,
+    );
+    factory_hashmap.insert(
+        Command::QueueMove(String::default(), 0).to_string(),
+        build_queue_move_command,
+    );
+    factory_hashmap.insert(Command::QueueClear.to_string(), build_queue_clear_command);
+    factory_hashmap.insert(Command::Queue.to_string(), build_queue_command);
+    factory_hashmap.insert(Command::Next.to_string(), build_next_command);
+    factory_hashmap.insert(Command::Playlist(None).to_string(), build_playlist_command);
+    factory_hashmap.insert(
+        Command::PlayPlaylist(String::default()).to_string(),
+        build_play_playlist_command,
+    );
+    factory_hashmap.insert(
+        Command::SavePlaylist(String::default(), vec![]).to_string(),
+        build_save_playlist_command,
+    );
+    factory_hashmap.insert(
+        Command::DeletePlaylist(String::default()).to_string(),
+        build_delete_playlist_command,
+    );
+    factory_hashmap.insert(Command::Chapters.to_string(), build_chapters_command);
+    factory_hashmap.insert(Command::NextChapter.to_string(), build_next_chapter_command);
+    factory_hashmap.insert(
+        Command::PreviousChapter.to_string(),
+        build_previous_chapter_command,
+    );
     factory_hashmap
 }
 
@@ -252,4 +674,102 @@ mod tests {
     fn test_is_hash(hash: &str) -> bool {
         is_hash(hash)
     }
+
+    #[test_case(vec!["a9"] => Ok(Command::Select("a9".to_owned())); "Hash prefix")]
+    #[test_case(vec!["a99e84"] => Ok(Command::Select("a99e84".to_owned())); "Full hash")]
+    #[test_case(vec!["a99e84g"] => Err(()); "Not an hexadecimal number")]
+    #[test_case(vec![&"a".repeat(41)] => Err(()); "Longer than a persistent key")]
+    fn test_build_select_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_select_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
+
+    #[test]
+    fn test_build_set_feed_header_command() {
+        let parameters = [
+            "https://www.example.com/feed.xml",
+            "Authorization",
+            "Bearer",
+            "abc",
+        ]
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+        let command = build_set_feed_header_command(parameters).unwrap();
+        assert_eq!(
+            command,
+            Command::SetFeedHeader(
+                CommandUrl(Url::parse("https://www.example.com/feed.xml").unwrap()),
+                "Authorization".to_owned(),
+                "Bearer abc".to_owned()
+            )
+        );
+        assert!(build_set_feed_header_command(vec!["not an url".to_owned(); 3]).is_err());
+    }
+
+    #[test_case(vec!["https://www.example.com/feed.xml", "My", "podcast"] => Ok(Command::SetAlias(CommandUrl(Url::parse("https://www.example.com/feed.xml").unwrap()), Some("My podcast".to_owned()))); "Alias with spaces")]
+    #[test_case(vec!["https://www.example.com/feed.xml"] => Ok(Command::SetAlias(CommandUrl(Url::parse("https://www.example.com/feed.xml").unwrap()), None)); "No alias")]
+    #[test_case(vec![] => Err(()); "No URL")]
+    fn test_build_set_alias_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_set_alias_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
+
+    #[test_case(vec!["a1b2c3"] => Ok(EpisodeFilter { podcasts: vec!["a1b2c3".to_owned()], ..Default::default() }); "Podcast hash")]
+    #[test_case(vec!["--unfinished", "--since", "7d", "--podcast", "a1b2c3", "--sort", "duration", "--limit", "20"] => Ok(EpisodeFilter {
+        podcasts: vec!["a1b2c3".to_owned()],
+        status: Some(ListeningStatus::Unfinished),
+        since: Some(chrono::Duration::days(7)),
+        sort_key: EpisodeSortKey::Duration,
+        reverse: false,
+        limit: Some(20),
+    }); "All options")]
+    #[test_case(vec!["--podcast", "a1b2c3", "--podcast", "d4e5f6"] => Ok(EpisodeFilter { podcasts: vec!["a1b2c3".to_owned(), "d4e5f6".to_owned()], ..Default::default() }); "Several podcasts")]
+    #[test_case(vec!["--podcast", "https://www.example.com/feed.xml"] => Ok(EpisodeFilter { podcasts: vec!["https://www.example.com/feed.xml".to_owned()], ..Default::default() }); "Feed URL")]
+    #[test_case(vec!["--podcast", "news"] => Err(()); "Neither hash nor URL")]
+    #[test_case(vec!["--since", "12h", "--reverse"] => Ok(EpisodeFilter { since: Some(chrono::Duration::hours(12)), reverse: true, ..Default::default() }); "Age as a duration")]
+    #[test_case(vec!["--limit"] => Err(()); "Missing value")]
+    #[test_case(vec!["--sort", "loudness"] => Err(()); "Unknown sort key")]
+    #[test_case(vec!["--shuffle"] => Err(()); "Unknown option")]
+    fn test_parse_episode_filter(parameters: Vec<&str>) -> Result<EpisodeFilter, ()> {
+        let parameters = parameters
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>();
+        parse_episode_filter("list_episodes", &parameters).map_err(|_| ())
+    }
+
+    #[test_case(vec!["\"black", "holes\"", "light"] => Ok(Command::Search("\"black holes\" light".to_owned())); "Quoted phrase")]
+    #[test_case(vec![] => Err(()); "No terms")]
+    fn test_build_search_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_search_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
+
+    #[test_case(vec!["a99e"] => Ok(Command::See("a99e".to_owned())); "Hash prefix")]
+    #[test_case(vec!["not_a_hash"] => Err(()); "Invalid hash")]
+    #[test_case(vec!["a99e", "02a0"] => Err(()); "Too many hashes")]
+    fn test_build_see_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_see_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
+
+    #[test_case(vec!["a99e", "2"] => Ok(Command::QueueMove("a99e".to_owned(), 2)); "Hash and position")]
+    #[test_case(vec!["a99e", "0"] => Err(()); "Positions start at 1")]
+    #[test_case(vec!["a99e", "first"] => Err(()); "Position not a number")]
+    #[test_case(vec!["a99e"] => Err(()); "Missing position")]
+    fn test_build_queue_move_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_queue_move_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
+
+    #[test_case(vec!["news", "--unfinished", "--since", "3d"] => Ok(Command::SavePlaylist("news".to_owned(), vec!["--unfinished".to_owned(), "--since".to_owned(), "3d".to_owned()])); "Name and options")]
+    #[test_case(vec!["everything"] => Ok(Command::SavePlaylist("everything".to_owned(), vec![])); "No options")]
+    #[test_case(vec!["--unfinished", "news"] => Err(()); "Missing name")]
+    #[test_case(vec!["news", "--since", "yesterday"] => Err(()); "Invalid options")]
+    fn test_build_save_playlist_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_save_playlist_command(parameters.iter().map(|p| p.to_string()).collect())
+            .map_err(|_| ())
+    }
+
+    #[test_case(vec!["/home/user/Audiobooks/My", "book"] => Ok(Command::AddFolder("/home/user/Audiobooks/My book".to_owned())); "Path with a space")]
+    #[test_case(vec![] => Err(()); "No path")]
+    fn test_build_add_folder_command(parameters: Vec<&str>) -> Result<Command, ()> {
+        build_add_folder_command(parameters.iter().map(|p| p.to_string()).collect()).map_err(|_| ())
+    }
 }
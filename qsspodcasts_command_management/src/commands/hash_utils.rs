@@ -1,3 +1,13 @@
+/// Length of the persistent key of an episode, which is the longest hash prefix accepted
+const MAX_HASH_PREFIX_LEN: usize = 40;
+
 pub fn is_hash(hash: &str) -> bool {
     i64::from_str_radix(hash, 16).is_ok()
 }
+
+/// Tells whether the parameter can be the start of a hash, in which case it is resolved against the library
+pub fn is_hash_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix.len() <= MAX_HASH_PREFIX_LEN
+        && prefix.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -1,6 +1,6 @@
 use super::command_help_library::{CommandHelpLibrary, CommandHelpMap};
 use super::command_help_register::CommandHelpRegister;
-use crate::commands::command_enum::{Command, CommandDuration, CommandUrl};
+use crate::commands::command_enum::{Command, CommandDuration, CommandUrl, EpisodeFilter};
 
 pub fn get_command_help_library() -> CommandHelpLibrary {
     let map =
@@ -21,21 +21,69 @@ pub fn get_command_help_library() -> CommandHelpLibrary {
      CommandHelpRegister::new("play", "play [HASH]",
      "Launches the podcast",
     Some("If no hash if given, resumes the selected podcast.
-         If a hash is given, selects the associated podcast and launches it.")
+         If a hash is given, selects the associated podcast and launches it.
+         Like for select, any prefix of the hash matching a single episode is accepted.")
     )),
     (Command::Pause.to_string(), CommandHelpRegister::new("pause", "pause", "Pauses the player",
     None)),
     (Command::ListPodcasts.to_string(),
      CommandHelpRegister::new("list_podcasts", "list_podcasts", "Lists all subscribed podcasts",
                                                                 None)),
-    (Command::ListEpisodes(None).to_string(),
-     CommandHelpRegister::new("list_episodes", "list_episodes [HASH]", "Lists episodes and some information about them, including their hashes",
+    (Command::ListEpisodes(EpisodeFilter::default()).to_string(),
+     CommandHelpRegister::new("list_episodes", "list_episodes [HASH] [OPTIONS]", "Lists episodes and some information about them, including their hashes",
                                                                 Some("If no hash is given, lists all episodes of all subscribed podcasts, sorted by release date.
-                                                                If a podcast hash is given, lists all episodes for the given podcast.")),
+                                                                If a podcast hash is given, lists all episodes for the given podcast. Any prefix of the hash matching a single podcast is accepted.
+                                                                Options can be combined to select and order the episodes (e.g. : list_episodes --unfinished --since 7d --sort duration --limit 20) :
+                                                                - --podcast <HASH|URL> : same as giving the hash of the podcast, or the URL of its feed, can be repeated to list episodes of several podcasts
+                                                                - --finished, --unfinished, --in_progress : keeps the episodes in the given state
+                                                                - --since <AGE> : keeps the episodes released less than <AGE> ago (e.g. : 7d, 2w, 12h)
+                                                                - --sort <KEY> : sorts by date (newest first), duration (shortest first), title or podcast
+                                                                - --reverse : reverses the order
+                                                                - --limit <COUNT> : only lists the first <COUNT> episodes")),
     ),
-    (Command::AddRss(CommandUrl::default()).to_string(), CommandHelpRegister::new("add_rss", "add_rss <URL>", "register the RSS feed whose URL is given in parameter", None)),
-    (Command::DeleteRss(String::default()).to_string(), CommandHelpRegister::new("delete_rss", "delete_rss <HASH>", "Delete the RSS feed matching the podcast hash given in parameter", None)),
-    (Command::Select(String::default()).to_string(), CommandHelpRegister::new("select", "select <HASH>", "Selects a podcast", Some("Selects a podcast, allowing to play it"))),
+    (Command::Search(String::default()).to_string(),
+     CommandHelpRegister::new("search", "search <TERMS>",
+     "Searches the episodes",
+     Some("Lists the episodes whose title, podcast title, author or description contain all the given terms, the most relevant first.
+          Terms between double quotes (e.g. : \"black holes\") must appear in a row. Case and punctuation are ignored.")
+    )),
+    (Command::AddRss(CommandUrl::default()).to_string(), CommandHelpRegister::new("add_rss", "add_rss <URL>", "register the RSS feed whose URL is given in parameter",
+                                                                Some("If the URL is the one of a web page, the RSS and Atom feeds it advertises are looked for.
+                                                                If there is only one of them, it is subscribed to, else a window offers to pick one."))),
+    (Command::AddFolder(String::default()).to_string(),
+     CommandHelpRegister::new("add_folder", "add_folder <PATH>",
+     "Registers a local folder as a podcast",
+     Some("The audio files stored in the folder whose path is given in parameter become the episodes of the podcast.
+          Their title, author and release date are read from their ID3 tags, the modification date of the file being used if there is none.
+          Subfolders are not explored.")
+    )),
+    (Command::DeleteRss(String::default()).to_string(), CommandHelpRegister::new("delete_rss", "delete_rss <HASH>", "Delete the RSS feed matching the podcast hash given in parameter",
+     Some("Any prefix of the hash matching a single podcast is accepted."))),
+    (Command::Refresh(None).to_string(),
+     CommandHelpRegister::new("refresh", "refresh [HASH]",
+     "Fetches the feeds again to get the latest episodes",
+     Some("If a podcast hash is given, only the feed of this podcast is fetched again.
+          Else all subscribed feeds are. The episodes of the podcasts are replaced by the ones of their feed.")
+    )),
+    (Command::LoadArchive(String::default()).to_string(),
+     CommandHelpRegister::new("load_archive", "load_archive <HASH>",
+     "Loads the older episodes of the podcast",
+     Some("Many hosts only keep the latest episodes in the feed, and put the older ones in archive pages.
+          The pages linked by the feed (rel=\"next\" or rel=\"prev-archive\" links) are downloaded one after another, and the episodes they contain are added to the podcast whose hash is given in parameter.
+          Older episodes are kept until the podcast is refreshed with the refresh command or the application is restarted. The periodic background refresh keeps them.")
+    )),
+    (Command::Select(String::default()).to_string(), CommandHelpRegister::new("select", "select <HASH>", "Selects a podcast",
+     Some("Selects a podcast, allowing to play it.
+          Any prefix of the hash matching a single episode is accepted. If several episodes share the same hash, the start of their persistent key, listed in the error, can be given instead.
+          Episodes sharing their persistent key as well, which happens when a feed repeats an item, cannot be told apart and cannot be selected.
+          When such an episode is queued, the first one found in the library is played."))),
+    (Command::See(String::default()).to_string(),
+     CommandHelpRegister::new("see", "see <HASH>",
+     "Displays the details of an episode or a podcast",
+     Some("For an episode, displays its whole description, the links of its show notes, its categories, author, release date, the size and type of its audio file, whether it is downloaded and how far it was listened to.
+          For a podcast, displays its description, the URL of its feed and how many of its episodes are downloaded, started and finished.
+          Any prefix of the hash matching a single episode or podcast is accepted.")
+    )),
     (Command::Advance(CommandDuration::default()).to_string(), CommandHelpRegister::new("advance", "advance <DURATION>", "Advances the podcast of the given duration",
                                                                                         Some("Advances the podcast of the duration given in parameter.
                                                                                         The duration can be expressed a lot of ways, including :
@@ -72,6 +120,129 @@ pub fn get_command_help_library() -> CommandHelpLibrary {
      CommandHelpRegister::new("set_volume", "set_volume <INTEGER>",
      "Sets the volume to <INTEGER> percents of the max volume",
      None
+    )),
+    (Command::ImportOpml(String::default()).to_string(),
+     CommandHelpRegister::new("import_opml", "import_opml <PATH>",
+     "Subscribes to all feeds listed in the given OPML file",
+     Some("Reads the OPML file whose path is given in parameter and registers every feed it contains.
+          Feeds already subscribed to are skipped, and a summary of the import is displayed.")
+    )),
+    (Command::ExportOpml(String::default()).to_string(),
+     CommandHelpRegister::new("export_opml", "export_opml <PATH>",
+     "Writes all subscriptions in an OPML file",
+     None
+    )),
+    (Command::CheckFeeds.to_string(),
+     CommandHelpRegister::new("check_feeds", "check_feeds",
+     "Downloads every feed again and reports what went wrong with them",
+     Some("For each subscribed feed, displays the HTTP status, the redirections followed, the content type,
+          the parsing error if any, the number of items, and the items that could not be turned into episodes along with the reason why.
+          The local cache of the feeds is not used.")
+    )),
+    (Command::SetFeedAuth(CommandUrl::default(), String::default(), String::default()).to_string(),
+     CommandHelpRegister::new("set_feed_auth", "set_feed_auth <URL> <USERNAME> <PASSWORD>",
+     "Uses HTTP basic authentication for the feed and its episodes",
+     Some("Credentials are stored apart from the subscriptions, in a file only readable by its owner.
+          They are used to download the feed whose URL is given in parameter as well as its episodes.
+          The feed does not need to be subscribed to yet, which allows to add private feeds.")
+    )),
+    (Command::SetFeedHeader(CommandUrl::default(), String::default(), String::default()).to_string(),
+     CommandHelpRegister::new("set_feed_header", "set_feed_header <URL> <NAME> <VALUE>",
+     "Sends an extra header when downloading the feed and its episodes",
+     Some("Headers are stored along with the credentials of the feed, in a file only readable by its owner.
+          The value of the header can contain spaces.")
+    )),
+    (Command::ClearFeedAuth(CommandUrl::default()).to_string(),
+     CommandHelpRegister::new("clear_feed_auth", "clear_feed_auth <URL>",
+     "Removes the credentials and extra headers of the feed",
+     None
+    )),
+    (Command::SetAlias(CommandUrl::default(), None).to_string(),
+     CommandHelpRegister::new("set_alias", "set_alias <URL> [ALIAS]",
+     "Displays the podcast under another name",
+     Some("The alias is displayed instead of the title of the feed in the list of podcasts, and exported in OPML files.
+          It can contain spaces. If no alias is given, the current one is removed.")
+    )),
+    (Command::PauseFeed(CommandUrl::default()).to_string(),
+     CommandHelpRegister::new("pause_feed", "pause_feed <URL>",
+     "Stops fetching the feed without unsubscribing from it",
+     Some("The podcast is not refreshed anymore, and is left out of the library from the next start of the application.
+          Downloaded episodes and listening progression are kept.")
+    )),
+    (Command::ResumeFeed(CommandUrl::default()).to_string(),
+     CommandHelpRegister::new("resume_feed", "resume_feed <URL>",
+     "Fetches the paused feed again",
+     Some("The podcast comes back in the library at the next refresh.")
+    )),
+    (Command::QueueAdd(String::default()).to_string(),
+     CommandHelpRegister::new("queue_add", "queue_add <HASH>",
+     "Adds the episode at the end of the queue",
+     Some("Queued episodes are played one after another : when an episode is finished, the first one of the queue is removed from it and played.
+          The queue is kept when the application is closed.")
+    )),
+    (Command::QueueRemove(String::default()).to_string(),
+     CommandHelpRegister::new("queue_remove", "queue_remove <HASH>",
+     "Removes the episode from the queue",
+     None
+    )),
+    (Command::QueueMove(String::default(), 0).to_string(),
+     CommandHelpRegister::new("queue_move", "queue_move <HASH> <POSITION>",
+     "Moves the queued episode at the given position",
+     Some("Positions start at 1, which is the next episode played. Positions past the end of the queue move the episode last.")
+    )),
+    (Command::QueueClear.to_string(),
+     CommandHelpRegister::new("queue_clear", "queue_clear",
+     "Removes all episodes from the queue",
+     None
+    )),
+    (Command::Queue.to_string(),
+     CommandHelpRegister::new("queue", "queue",
+     "Lists the queued episodes, the next one first",
+     None
+    )),
+    (Command::Next.to_string(),
+     CommandHelpRegister::new("next", "next",
+     "Plays the first episode of the queue",
+     Some("The episode is removed from the queue, and the progression of the current episode is saved.")
+    )),
+    (Command::Playlist(None).to_string(),
+     CommandHelpRegister::new("playlist", "playlist [NAME]",
+     "Lists the episodes of the playlist",
+     Some("Episodes of playlists are selected again each time they are listed or played, so that new episodes are included.
+          If no name is given, lists the names of the saved playlists.")
+    )),
+    (Command::PlayPlaylist(String::default()).to_string(),
+     CommandHelpRegister::new("play_playlist", "play_playlist <NAME>",
+     "Plays the episodes of the playlist one after another",
+     Some("The episodes of the playlist are put at the front of the queue, before the episodes already queued, and the first one is played.")
+    )),
+    (Command::SavePlaylist(String::default(), vec![]).to_string(),
+     CommandHelpRegister::new("save_playlist", "save_playlist <NAME> [OPTIONS]",
+     "Saves a playlist whose episodes are selected by the given options",
+     Some("Options are the ones of list_episodes (e.g. : save_playlist news --unfinished --since 3d --podcast a1b2c3 --podcast d4e5f6).
+          Podcasts are saved as the URLs of their feeds, so that the playlist keeps working when their hashes change.
+          They follow the feeds that move, and are removed from the playlist when unsubscribed from, playlists left without any podcast being deleted.
+          Saving a playlist with the name of an existing one replaces it. Playlists are kept when the application is closed.")
+    )),
+    (Command::DeletePlaylist(String::default()).to_string(),
+     CommandHelpRegister::new("delete_playlist", "delete_playlist <NAME>",
+     "Deletes the playlist",
+     None
+    )),
+    (Command::Chapters.to_string(),
+     CommandHelpRegister::new("chapters", "chapters",
+     "Lists the chapters of the selected episode",
+     Some("Chapters are read from the ID3 tag of the audio file, or else from the chapters file referenced by the feed.")
+    )),
+    (Command::NextChapter.to_string(),
+     CommandHelpRegister::new("next_chapter", "next_chapter",
+     "Goes to the beginning of the next chapter",
+     None
+    )),
+    (Command::PreviousChapter.to_string(),
+     CommandHelpRegister::new("previous_chapter", "previous_chapter",
+     "Goes back to the beginning of the current chapter",
+     Some("If the current chapter started less than 3 seconds ago, goes back to the beginning of the previous one instead.")
     ))],
 
     );
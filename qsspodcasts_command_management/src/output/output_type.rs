@@ -1,5 +1,11 @@
 use crate::commands::helps::command_help::CommandHelp;
-use podcast_management::data_objects::{podcast::Podcast, podcast_episode::PodcastEpisode};
+use podcast_management::data_objects::{
+    chapter::Chapter, episode_details::EpisodeDetails, podcast::Podcast,
+    podcast_details::PodcastDetails, podcast_episode::PodcastEpisode,
+};
+use rss_management::rss_feed_reading::{
+    feed_diagnostic::FeedDiagnostic, feed_discovery::FeedCandidate,
+};
 
 #[derive(Clone)]
 pub enum OutputType {
@@ -8,6 +14,11 @@ pub enum OutputType {
     Episodes(Vec<PodcastEpisode>),
     CommandHelps(Vec<CommandHelp>),
     RawString(String),
+    FeedDiagnostics(Vec<FeedDiagnostic>),
+    FeedCandidates(Vec<FeedCandidate>),
+    EpisodeDetails(Box<EpisodeDetails>),
+    PodcastDetails(Box<PodcastDetails>),
+    Chapters(Vec<Chapter>),
 }
 
 impl PartialEq for OutputType {
@@ -18,6 +29,11 @@ impl PartialEq for OutputType {
                 | (Self::Episodes(_), Self::Episodes(_))
                 | (Self::CommandHelps(_), Self::CommandHelps(_))
                 | (Self::RawString(_), Self::RawString(_))
+                | (Self::FeedDiagnostics(_), Self::FeedDiagnostics(_))
+                | (Self::FeedCandidates(_), Self::FeedCandidates(_))
+                | (Self::EpisodeDetails(_), Self::EpisodeDetails(_))
+                | (Self::PodcastDetails(_), Self::PodcastDetails(_))
+                | (Self::Chapters(_), Self::Chapters(_))
                 | (Self::None, Self::None)
         )
     }
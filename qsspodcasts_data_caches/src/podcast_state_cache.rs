@@ -1,27 +1,59 @@
 use podcast_management::data_objects::podcast_state::PodcastState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 type Hash = String;
 
 #[derive(Default)]
 pub struct PodcastStateCache {
     states: HashMap<Hash, PodcastState>,
+    /// Episodes listed at the previous launch, unknown if the application was never launched before
+    seen_episodes: Option<HashSet<Hash>>,
 }
 
 impl PodcastStateCache {
-    pub fn new(podcast_states: HashMap<Hash, PodcastState>) -> Self {
+    pub fn new(
+        podcast_states: HashMap<Hash, PodcastState>,
+        seen_episodes: Option<HashSet<Hash>>,
+    ) -> Self {
         Self {
             states: podcast_states,
+            seen_episodes,
         }
     }
 
-    pub fn get_podcast_state(&self, hash: &Hash) -> Option<&PodcastState> {
-        self.states.get(hash)
+    /// Returns the state of the episode, which is new if it has no other state and was not seen before
+    pub fn get_podcast_state(&self, hash: &Hash) -> PodcastState {
+        if let Some(state) = self.states.get(hash) {
+            return *state;
+        }
+        match &self.seen_episodes {
+            Some(seen_episodes) if !seen_episodes.contains(hash) => PodcastState::New,
+            _ => PodcastState::Undefined,
+        }
     }
 
     pub fn set_podcast_state(&mut self, hash: &Hash, state: &PodcastState) {
         self.states.insert(hash.clone(), *state);
     }
+
+    /// Records that the audio file of the episode was downloaded, which does not matter anymore once it is started
+    pub fn on_episode_downloaded(&mut self, hash: &Hash) {
+        let state = match self.get_podcast_state(hash) {
+            PodcastState::Archived => PodcastState::Finished,
+            PodcastState::InProgress(_) | PodcastState::Finished => return,
+            _ => PodcastState::Downloaded,
+        };
+        self.set_podcast_state(hash, &state);
+    }
+
+    /// Records the position the listening of the episode was stopped at, unless it was finished before
+    pub fn on_progression_saved(&mut self, hash: &Hash, position: Duration) {
+        match self.get_podcast_state(hash) {
+            PodcastState::Finished | PodcastState::Archived => (),
+            _ => self.set_podcast_state(hash, &PodcastState::InProgress(position)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -32,14 +64,58 @@ pub mod tests {
     #[test]
     fn test_state_change() {
         let states: HashMap<Hash, PodcastState> = HashMap::with_capacity(10);
-        let mut cache = PodcastStateCache::new(states);
+        let mut cache = PodcastStateCache::new(states, None);
         let hash = "111111".to_owned();
 
         cache.set_podcast_state(&hash, &PodcastState::Finished);
 
+        assert_eq!(cache.get_podcast_state(&hash), PodcastState::Finished)
+    }
+
+    #[test]
+    fn test_new_episodes() {
+        let states = HashMap::from([("333333".to_owned(), PodcastState::Downloaded)]);
+        let cache = PodcastStateCache::new(states.clone(), None);
+        assert_eq!(
+            cache.get_podcast_state(&"111111".to_owned()),
+            PodcastState::Undefined
+        );
+
+        let seen_episodes = HashSet::from(["111111".to_owned()]);
+        let cache = PodcastStateCache::new(states, Some(seen_episodes));
+        assert_eq!(
+            cache.get_podcast_state(&"111111".to_owned()),
+            PodcastState::Undefined
+        );
+        assert_eq!(
+            cache.get_podcast_state(&"222222".to_owned()),
+            PodcastState::New
+        );
         assert_eq!(
-            cache.get_podcast_state(&hash).unwrap(),
-            &PodcastState::Finished
-        )
+            cache.get_podcast_state(&"333333".to_owned()),
+            PodcastState::Downloaded
+        );
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        let hash = "111111".to_owned();
+        let mut cache = PodcastStateCache::new(HashMap::new(), Some(HashSet::new()));
+
+        cache.on_episode_downloaded(&hash);
+        assert_eq!(cache.get_podcast_state(&hash), PodcastState::Downloaded);
+
+        cache.on_progression_saved(&hash, Duration::from_secs(120));
+        cache.on_episode_downloaded(&hash);
+        assert_eq!(
+            cache.get_podcast_state(&hash),
+            PodcastState::InProgress(Duration::from_secs(120))
+        );
+
+        cache.set_podcast_state(&hash, &PodcastState::Archived);
+        cache.on_progression_saved(&hash, Duration::from_secs(60));
+        assert_eq!(cache.get_podcast_state(&hash), PodcastState::Archived);
+        cache.on_episode_downloaded(&hash);
+        assert_eq!(cache.get_podcast_state(&hash), PodcastState::Finished);
     }
 }
@@ -1,7 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use fs_utils::progression_read_utils::read_progression_in_file;
 use log;
 
 use path_providing::path_provider::PathProvider;
@@ -21,26 +24,23 @@ pub struct CommandError {
     _kind: ErrorKind,
 }
 
+/// Finished markers are named after the persistent key of the episodes, or their legacy hash if they were not migrated
 fn is_hash(hash: &str) -> bool {
-    i64::from_str_radix(hash, 16).is_ok()
+    !hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit())
 }
 
-/// # TODO
-///   - Add an explicit error type instead of just unit type
-pub async fn build_podcast_state_cache<P: PathProvider + Send + Sync>(
-    path_provider: Arc<P>,
-) -> Result<PodcastStateCache, ()> {
-    let finished_podcasts_dir = path_provider.finished_podcasts_dir_path();
-    let mut dir = if let Ok(d) = tokio::fs::read_dir(&finished_podcasts_dir).await {
+/// Returns the names of the files of the directory that are named after the key of an episode
+async fn read_keys_in_dir(dir_path: &Path) -> Result<Vec<String>, ()> {
+    let mut dir = if let Ok(d) = tokio::fs::read_dir(dir_path).await {
         d
     } else {
         log::error!(
             "Failed to read content of dir {}",
-            &finished_podcasts_dir.to_str().unwrap()
+            dir_path.to_str().unwrap()
         );
         return Err(());
     };
-    let mut states: HashMap<String, PodcastState> = HashMap::with_capacity(100);
+    let mut keys = vec![];
     while let Some(entry) = dir.next_entry().await.unwrap() {
         let file_type = entry
             .file_type()
@@ -50,13 +50,134 @@ pub async fn build_podcast_state_cache<P: PathProvider + Send + Sync>(
             let file_name = entry.file_name();
             let file_name = file_name.to_str().unwrap();
             if is_hash(file_name) {
-                states.insert(file_name.to_owned(), PodcastState::Finished);
+                keys.push(file_name.to_owned());
             }
         } else {
-            log::info!("Unknown file type in finished podcasts' directory");
+            log::info!(
+                "Unknown file type in directory {}",
+                dir_path.to_str().unwrap()
+            );
         }
     }
+    Ok(keys)
+}
 
-    let cache = PodcastStateCache::new(states);
+/// Returns the keys of the episodes whose audio file is downloaded, the files being named `<PODCAST>_<KEY>`
+async fn read_downloaded_keys(download_dir_path: &Path) -> HashSet<String> {
+    let mut dir = match tokio::fs::read_dir(download_dir_path).await {
+        Ok(d) => d,
+        Err(e) => {
+            log::info!("Could not read downloads directory : {}", e);
+            return HashSet::new();
+        }
+    };
+    let mut keys = HashSet::new();
+    while let Ok(Some(entry)) = dir.next_entry().await {
+        let file_name = entry.file_name();
+        let key = file_name
+            .to_str()
+            .and_then(|n| n.rsplit_once('_'))
+            .map(|(_, key)| key)
+            .filter(|key| is_hash(key));
+        if let Some(key) = key {
+            keys.insert(key.to_owned());
+        }
+    }
+    keys
+}
+
+/// Reads the keys of the episodes listed at the previous launch, one per line.
+///
+/// Returns None if the application never stored them, in which case no episode can be told new.
+async fn read_seen_episodes(file_path: &Path) -> Option<HashSet<String>> {
+    let content = tokio::fs::read_to_string(file_path).await.ok()?;
+    Some(
+        content
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_owned())
+            .collect(),
+    )
+}
+
+/// Derives the state of the episodes from what is stored about them, finishing an episode prevailing
+/// over starting it, which prevails over downloading it
+fn merge_states(
+    finished: Vec<String>,
+    downloaded: HashSet<String>,
+    progressions: HashMap<String, Duration>,
+) -> HashMap<String, PodcastState> {
+    let mut states: HashMap<String, PodcastState> = HashMap::with_capacity(100);
+    for key in &downloaded {
+        states.insert(key.clone(), PodcastState::Downloaded);
+    }
+    for (key, position) in progressions {
+        states.insert(key, PodcastState::InProgress(position));
+    }
+    for key in finished {
+        let state = if downloaded.contains(&key) {
+            PodcastState::Finished
+        } else {
+            PodcastState::Archived
+        };
+        states.insert(key, state);
+    }
+    states
+}
+
+/// # TODO
+///   - Add an explicit error type instead of just unit type
+pub async fn build_podcast_state_cache<P: PathProvider + Send + Sync>(
+    path_provider: Arc<P>,
+) -> Result<PodcastStateCache, ()> {
+    let finished = read_keys_in_dir(&path_provider.finished_podcasts_dir_path()).await?;
+    let downloaded = read_downloaded_keys(&path_provider.download_dir_path()).await;
+    let mut progressions = HashMap::new();
+    let progresses_dir_path = path_provider.podcast_progresses_dir_path();
+    for key in read_keys_in_dir(&progresses_dir_path)
+        .await
+        .unwrap_or_default()
+    {
+        if let Some(position) =
+            read_progression_in_file(path_provider.podcast_progress_file_path(&key)).await
+        {
+            progressions.insert(key, position);
+        }
+    }
+    let seen_episodes = read_seen_episodes(&path_provider.seen_episodes_file_path()).await;
+
+    let states = merge_states(finished, downloaded, progressions);
+    let cache = PodcastStateCache::new(states, seen_episodes);
     Ok(cache)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_states() {
+        let finished = vec!["aaaaaa".to_owned(), "bbbbbb".to_owned()];
+        let downloaded = HashSet::from([
+            "aaaaaa".to_owned(),
+            "cccccc".to_owned(),
+            "dddddd".to_owned(),
+        ]);
+        let progressions = HashMap::from([
+            ("bbbbbb".to_owned(), Duration::from_secs(30)),
+            ("cccccc".to_owned(), Duration::from_secs(60)),
+        ]);
+
+        let states = merge_states(finished, downloaded, progressions);
+
+        assert_eq!(states["aaaaaa"], PodcastState::Finished);
+        assert_eq!(states["bbbbbb"], PodcastState::Archived);
+        assert_eq!(
+            states["cccccc"],
+            PodcastState::InProgress(Duration::from_secs(60))
+        );
+        assert_eq!(states["dddddd"], PodcastState::Downloaded);
+        assert_eq!(states.len(), 4);
+    }
+}
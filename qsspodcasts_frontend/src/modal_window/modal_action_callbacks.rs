@@ -1,7 +1,8 @@
-use command_management::commands::command_enum::Command;
+use command_management::commands::command_enum::{Command, CommandUrl, EpisodeFilter};
 use podcast_management::data_objects::hashable::Hashable;
 use podcast_management::data_objects::podcast::Podcast;
 use podcast_management::data_objects::podcast_episode::PodcastEpisode;
+use rss_management::rss_feed_reading::feed_discovery::FeedCandidate;
 
 pub type CallbackReturnType = Result<(), ()>;
 pub type BuildCommandCallback<'a, T> = dyn Fn(&'a T) -> String;
@@ -11,7 +12,17 @@ pub fn build_play_command(episode: &PodcastEpisode) -> String {
     format!("{} {}", play_command, episode.hash())
 }
 
+pub fn build_queue_add_command(episode: &PodcastEpisode) -> String {
+    let queue_add_command = Command::QueueAdd(String::default()).to_string();
+    format!("{} {}", queue_add_command, episode.hash())
+}
+
 pub fn build_list_episodes_command(podcast: &Podcast) -> String {
-    let list_command = Command::ListEpisodes(None).to_string();
+    let list_command = Command::ListEpisodes(EpisodeFilter::default()).to_string();
     format!("{} {}", list_command, podcast.hash())
 }
+
+pub fn build_add_rss_command(candidate: &FeedCandidate) -> String {
+    let add_rss_command = Command::AddRss(CommandUrl::default()).to_string();
+    format!("{} {}", add_rss_command, candidate.url)
+}
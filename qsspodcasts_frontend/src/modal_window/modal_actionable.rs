@@ -1,16 +1,21 @@
 use super::modal_action_callbacks::*;
 use super::modal_action_data::ModalActionData;
 use command_management::commands::helps::command_help::CommandHelp;
-use podcast_management::data_objects::{podcast::Podcast, podcast_episode::PodcastEpisode};
+use podcast_management::data_objects::{
+    chapter::Chapter, podcast::Podcast, podcast_episode::PodcastEpisode,
+};
+use rss_management::rss_feed_reading::{
+    feed_diagnostic::FeedDiagnostic, feed_discovery::FeedCandidate,
+};
 
 pub trait ModalActionable {
-    fn get_action_list(&self) -> Vec<ModalActionData<Self>>
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
     where
         Self: Sized;
 }
 
 impl ModalActionable for Podcast {
-    fn get_action_list(&self) -> Vec<ModalActionData<Self>>
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
     where
         Self: Sized,
     {
@@ -23,23 +28,57 @@ impl ModalActionable for Podcast {
 }
 
 impl ModalActionable for PodcastEpisode {
-    fn get_action_list(&self) -> Vec<ModalActionData<Self>>
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
     where
         Self: Sized,
     {
-        vec![ModalActionData::new(
-            "Play".to_owned(),
-            self,
-            Box::new(build_play_command),
-        )]
+        vec![
+            ModalActionData::new("Play".to_owned(), self, Box::new(build_play_command)),
+            ModalActionData::new(
+                "Add to queue".to_owned(),
+                self,
+                Box::new(build_queue_add_command),
+            ),
+        ]
     }
 }
 
 impl ModalActionable for CommandHelp {
-    fn get_action_list(&self) -> Vec<ModalActionData<Self>>
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
+}
+
+impl ModalActionable for FeedDiagnostic {
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
+}
+
+impl ModalActionable for Chapter {
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
     where
         Self: Sized,
     {
         vec![]
     }
 }
+
+impl ModalActionable for FeedCandidate {
+    fn get_action_list(&self) -> Vec<ModalActionData<'_, Self>>
+    where
+        Self: Sized,
+    {
+        vec![ModalActionData::new(
+            format!("Subscribe to {}", self.display_name()),
+            self,
+            Box::new(build_add_rss_command),
+        )]
+    }
+}
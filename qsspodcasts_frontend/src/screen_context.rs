@@ -5,7 +5,7 @@ use std::time::Duration;
 
 use command_management::output::output_type::OutputType;
 use data_caches::PodcastStateCache;
-use podcast_management::data_objects::hashable::Hashable;
+use podcast_management::data_objects::chapter::Chapter;
 use podcast_player::player_status::PlayerStatus;
 
 use crate::autocompletion_context::AutocompletionContext;
@@ -14,6 +14,9 @@ use crate::modal_window::interactable_modal_window_context::InteractableModalWin
 use crate::modal_window::modal_action::ModalAction;
 use crate::modal_window::readonly_modal_context::ReadonlyModalContext;
 use crate::screen_action::ScreenAction;
+use crate::ui_drawers::output_management::detail_lines::{
+    build_episode_detail_lines, build_podcast_detail_lines,
+};
 
 use tui::widgets::ListState;
 
@@ -37,6 +40,8 @@ pub struct ScreenContext {
     pub(crate) read_only_modal_context: ReadonlyModalContext,
     pub(crate) podcasts_state_cache: PodcastStateCache,
     pub(crate) current_podcast_title: Option<String>,
+    /// Chapters of the selected episode
+    pub(crate) current_chapters: Vec<Chapter>,
     pub(crate) volume: u32,
 }
 
@@ -53,6 +58,11 @@ impl ScreenContext {
             OutputType::Episodes(l) => Some(l.len()),
             OutputType::Podcasts(l) => Some(l.len()),
             OutputType::CommandHelps(l) => Some(l.len()),
+            OutputType::FeedDiagnostics(l) => Some(l.len()),
+            OutputType::FeedCandidates(l) => Some(l.len()),
+            OutputType::Chapters(l) => Some(l.len()),
+            OutputType::EpisodeDetails(d) => Some(build_episode_detail_lines(d).len()),
+            OutputType::PodcastDetails(d) => Some(build_podcast_detail_lines(d).len()),
             _ => None,
         }
     }
@@ -66,6 +76,12 @@ impl ScreenContext {
             OutputType::Episodes(ref v) => builder.build_action_list(&v[index]),
             OutputType::Podcasts(ref v) => builder.build_action_list(&v[index]),
             OutputType::CommandHelps(ref v) => builder.build_action_list(&v[index]),
+            OutputType::FeedDiagnostics(ref v) => builder.build_action_list(&v[index]),
+            OutputType::FeedCandidates(ref v) => builder.build_action_list(&v[index]),
+            OutputType::Chapters(ref v) => builder.build_action_list(&v[index]),
+            // Whatever the line selected, the actions are the ones of the object detailed
+            OutputType::EpisodeDetails(ref d) => builder.build_action_list(&d.episode),
+            OutputType::PodcastDetails(ref d) => builder.build_action_list(&d.podcast),
             _ => unreachable!(),
         }
     }
@@ -74,12 +90,12 @@ impl ScreenContext {
         self.stacked_states.pop()
     }
 
-    /// Updates context when a podcast whose hash is given in parameter is finished
+    /// Updates context when a podcast whose persistent key is given in parameter is finished
     /// This can induce an cached output invalidation and a recomputing of it, to display the fact
     /// that the current podcast is finished
-    pub fn on_podcast_finished(&mut self, hash: &str) {
+    pub fn on_podcast_finished(&mut self, key: &str) {
         if let OutputType::Episodes(ref episodes) = self.last_command_output {
-            let matching_episodes = episodes.iter().filter(|e| e.hash() == hash);
+            let matching_episodes = episodes.iter().filter(|e| e.persistent_key() == key);
             assert_eq!(matching_episodes.clone().count(), 1);
             // TODO: Mark the podcast as finished ? Not here I think
         }
@@ -105,6 +121,7 @@ impl Default for ScreenContext {
             read_only_modal_context: ReadonlyModalContext::default(),
             podcasts_state_cache: PodcastStateCache::default(),
             current_podcast_title: None,
+            current_chapters: vec![],
             volume: 0,
         }
     }
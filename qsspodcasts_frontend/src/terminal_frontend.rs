@@ -94,6 +94,15 @@ impl<D: UiDrawer> Frontend<D> {
                 self.context.list_output_state =
                     Some(RefCell::new(ListState::default().with_selected(Some(0))));
             }
+            // Several feeds can be subscribed to, so the user is directly asked to pick one
+            if let OutputType::FeedCandidates(ref candidates) = output {
+                let actions = candidates
+                    .iter()
+                    .flat_map(|c| self.action_list_builder.build_action_list(c))
+                    .collect();
+                self.context.current_action = ScreenAction::ScrollingModalWindow;
+                self.context.interactable_modal_context.reset(Some(actions));
+            }
         }
     }
 
@@ -188,6 +197,12 @@ impl<D: UiDrawer> Frontend<D> {
                             OutputType::Episodes(ref v) => v.len(),
                             OutputType::Podcasts(ref v) => v.len(),
                             OutputType::CommandHelps(ref v) => v.len(),
+                            OutputType::FeedDiagnostics(ref v) => v.len(),
+                            OutputType::FeedCandidates(ref v) => v.len(),
+                            OutputType::Chapters(ref v) => v.len(),
+                            OutputType::EpisodeDetails(_) | OutputType::PodcastDetails(_) => {
+                                self.context.get_output_list_length().unwrap()
+                            }
                             _ => 0,
                         };
 
@@ -208,6 +223,12 @@ impl<D: UiDrawer> Frontend<D> {
                             OutputType::Episodes(ref v) => v.len(),
                             OutputType::Podcasts(ref v) => v.len(),
                             OutputType::CommandHelps(ref v) => v.len(),
+                            OutputType::FeedDiagnostics(ref v) => v.len(),
+                            OutputType::FeedCandidates(ref v) => v.len(),
+                            OutputType::Chapters(ref v) => v.len(),
+                            OutputType::EpisodeDetails(_) | OutputType::PodcastDetails(_) => {
+                                self.context.get_output_list_length().unwrap()
+                            }
                             _ => 0,
                         };
                         // TODO : Mutualize this properly
@@ -490,9 +511,29 @@ impl<D: UiDrawer> Frontend<D> {
                     // TODO: Add a condition to invalidate cache
                     self.context.must_invalidate_cache.set(true);
                 }
+                EpisodeDownloaded(key) => {
+                    self.context
+                        .podcasts_state_cache
+                        .on_episode_downloaded(&key);
+                    self.context.must_invalidate_cache.set(true);
+                }
+                ProgressionSaved { key, position } => {
+                    self.context
+                        .podcasts_state_cache
+                        .on_progression_saved(&key, position);
+                    self.context.must_invalidate_cache.set(true);
+                }
                 business_core::event_type::EventType::PodcastLaunched(title) => {
                     self.context.current_podcast_title = Some(title)
                 }
+                ChaptersLoaded(chapters) => self.context.current_chapters = chapters,
+                NewEpisodes { podcast, count } => {
+                    self.context
+                        .message_notifications_buffer
+                        .push_front(format!("{} new episode(s) of {}", count, podcast));
+                    self.context.message_notifications_buffer.truncate(4);
+                    self.context.must_invalidate_cache.set(true);
+                }
                 _ => {
                     error!("Received unhandled event {:?}", e);
                 }
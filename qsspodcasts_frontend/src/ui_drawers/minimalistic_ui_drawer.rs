@@ -1,6 +1,8 @@
 use command_management::output::output_type::OutputType;
 use log::debug;
+use podcast_management::data_objects::chapter::find_chapter_index;
 use podcast_management::data_objects::hashable::Hashable;
+use podcast_management::data_objects::podcast_episode::PodcastEpisode;
 use podcast_management::data_objects::podcast_state::PodcastState;
 use podcast_player::duration_wrapper::DurationWrapper;
 use podcast_player::player_status::PlayerStatus;
@@ -20,6 +22,9 @@ use tui::{
 
 use crate::screen_action::ScreenAction;
 use crate::screen_context::ScreenContext;
+use crate::ui_drawers::output_management::detail_lines::{
+    build_episode_detail_lines, build_podcast_detail_lines, DetailLine,
+};
 
 use str_to_lines::str_linesplit::str_to_lines;
 
@@ -128,11 +133,18 @@ impl MinimalisticUiDrawer<'_> {
         };
 
         let percentage = percentage.clamp(0, 100);
+        let label = match find_chapter_index(&context.current_chapters, *progress.inner_ref()) {
+            Some(i) => format!(
+                "{progress}/{duration} - {}",
+                context.current_chapters[i].title
+            ),
+            None => format!("{progress}/{duration}"),
+        };
 
         Gauge::default()
             .block(Block::default().title("").borders(Borders::ALL))
             .gauge_style(Style::default().fg(Color::LightYellow))
-            .label(format!("{progress}/{duration}"))
+            .label(label)
             .percent(percentage.into())
     }
 
@@ -168,6 +180,88 @@ impl MinimalisticUiDrawer<'_> {
             || context.previous_output_pane_available_width.get().unwrap() != available_width
     }
 
+    fn build_podcast_state_label<'b>(
+        state: &PodcastState,
+        episode: &PodcastEpisode,
+    ) -> Option<Span<'b>> {
+        let (label, style) = match state {
+            PodcastState::Undefined => return None,
+            PodcastState::New => (
+                "[NEW]".to_owned(),
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(Color::LightYellow),
+            ),
+            PodcastState::Downloaded => (
+                "[DOWNLOADED]".to_owned(),
+                Style::default().fg(Color::LightBlue),
+            ),
+            PodcastState::InProgress(position) => {
+                let progression = match state.compute_progression_percentage(episode.duration) {
+                    Some(percentage) => format!("{}%", percentage),
+                    None => DurationWrapper::new(*position).to_string(),
+                };
+                (
+                    format!("[IN PROGRESS {}]", progression),
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::LightMagenta),
+                )
+            }
+            PodcastState::Finished => (
+                "[FINISHED]".to_owned(),
+                Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+            ),
+            PodcastState::Archived => (
+                "[ARCHIVED]".to_owned(),
+                Style::default()
+                    .add_modifier(Modifier::ITALIC)
+                    .fg(Color::DarkGray),
+            ),
+        };
+        Some(Span::styled(label, style))
+    }
+
+    fn build_detail_list_items<'b>(
+        lines: Vec<DetailLine>,
+        available_width: usize,
+    ) -> Vec<ListItem<'b>> {
+        let label_style = Style::default().add_modifier(Modifier::ITALIC);
+        lines
+            .into_iter()
+            .map(|l| match l {
+                DetailLine::Title(title) => ListItem::new(Line::from(Span::styled(
+                    title,
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ))),
+                DetailLine::Field(label, value) => ListItem::new(Line::from(vec![
+                    Span::styled(format!("{}:", label), label_style),
+                    Span::from(" "),
+                    Span::from(value),
+                ])),
+                DetailLine::Section(name) => ListItem::new(Line::from(Span::styled(
+                    name.to_uppercase(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))),
+                DetailLine::Text(text) => {
+                    let lines = str_to_lines(&text, available_width)
+                        .into_iter()
+                        .map(Line::from)
+                        .collect::<Vec<Line>>();
+                    if lines.is_empty() {
+                        ListItem::new(Line::from(""))
+                    } else {
+                        ListItem::new(lines)
+                    }
+                }
+                DetailLine::Link(link) => ListItem::new(Line::from(vec![
+                    Span::raw("  - "),
+                    Span::styled(link, Style::default().fg(Color::LightBlue)),
+                ])),
+            })
+            .collect()
+    }
+
     /// TODO : Clean this awful shit
     fn build_output_field_list(&mut self, context: &ScreenContext, available_width: usize) -> List {
         if MinimalisticUiDrawer::is_output_cache_invalidated(context, available_width) {
@@ -203,18 +297,13 @@ impl MinimalisticUiDrawer<'_> {
                                         .add_modifier(Modifier::ITALIC),
                                 ),
                             ];
-                            if let Some(PodcastState::Finished) =
-                                context.podcasts_state_cache.get_podcast_state(&e.hash())
+                            let state = context
+                                .podcasts_state_cache
+                                .get_podcast_state(&e.persistent_key());
+                            if let Some(state_label) =
+                                MinimalisticUiDrawer::build_podcast_state_label(&state, e)
                             {
-                                metadata_display.append(&mut vec![
-                                    Span::from("    "),
-                                    Span::styled(
-                                        "[FINISHED]",
-                                        Style::default()
-                                            .add_modifier(Modifier::BOLD)
-                                            .fg(Color::Red),
-                                    ),
-                                ]);
+                                metadata_display.append(&mut vec![Span::from("    "), state_label]);
                             }
                             let metadata_display = iter::once(Line::from(metadata_display));
                             let vec_spans = vec_spans.chain(metadata_display);
@@ -242,7 +331,7 @@ impl MinimalisticUiDrawer<'_> {
                                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                             )));
 
-                            let hash_display = iter::once(Line::from(vec![
+                            let mut hash_display = vec![
                                 Span::from("["),
                                 Span::styled(
                                     p.hash(),
@@ -251,7 +340,19 @@ impl MinimalisticUiDrawer<'_> {
                                         .fg(Color::LightGreen),
                                 ),
                                 Span::from("]"),
-                            ]));
+                            ];
+                            if p.is_stale {
+                                hash_display.append(&mut vec![
+                                    Span::from("    "),
+                                    Span::styled(
+                                        "[STALE]",
+                                        Style::default()
+                                            .add_modifier(Modifier::BOLD)
+                                            .fg(Color::Yellow),
+                                    ),
+                                ]);
+                            }
+                            let hash_display = iter::once(Line::from(hash_display));
                             let vec_spans = vec_spans.chain(hash_display);
 
                             let description_style = Style::default().add_modifier(Modifier::ITALIC);
@@ -313,6 +414,128 @@ impl MinimalisticUiDrawer<'_> {
                         .collect::<Vec<ListItem>>();
                     self.cached_output = Cow::Owned(output);
                 }
+                OutputType::FeedDiagnostics(diagnostics) => {
+                    let output = diagnostics
+                        .iter()
+                        .map(move |d| {
+                            let url_color = if d.is_healthy() {
+                                Color::LightGreen
+                            } else {
+                                Color::Red
+                            };
+                            let mut vec_spans = vec![Line::from(Span::styled(
+                                d.url.clone(),
+                                Style::default().fg(url_color).add_modifier(Modifier::BOLD),
+                            ))];
+
+                            let label_style = Style::default().add_modifier(Modifier::ITALIC);
+                            let status =
+                                d.status.map(|s| s.to_string()).unwrap_or("None".to_owned());
+                            let content_type =
+                                d.content_type.clone().unwrap_or("Unknown".to_owned());
+                            vec_spans.push(Line::from(vec![
+                                Span::styled("Status:", label_style),
+                                Span::from(format!(" {}   ", status)),
+                                Span::styled("Content type:", label_style),
+                                Span::from(format!(" {}", content_type)),
+                            ]));
+                            for redirect in &d.redirects {
+                                vec_spans.push(Line::from(vec![
+                                    Span::styled("Redirected to:", label_style),
+                                    Span::from(format!(" {}", redirect)),
+                                ]));
+                            }
+                            if let Some(error) = &d.error {
+                                vec_spans.extend(
+                                    str_to_lines(&format!("Error: {}", error), available_width)
+                                        .into_iter()
+                                        .map(|s| {
+                                            Line::from(Span::styled(
+                                                s,
+                                                Style::default().fg(Color::Red),
+                                            ))
+                                        }),
+                                );
+                            }
+                            if let Some(item_count) = d.item_count {
+                                vec_spans.push(Line::from(vec![
+                                    Span::styled("Items:", label_style),
+                                    Span::from(format!(
+                                        " {}   ({} rejected)",
+                                        item_count,
+                                        d.rejected_items.len()
+                                    )),
+                                ]));
+                            }
+                            for (reason, count) in d.count_rejections_by_reason() {
+                                vec_spans.push(Line::from(vec![
+                                    Span::raw("    "),
+                                    Span::styled(
+                                        format!("{} item(s) rejected :", count),
+                                        Style::default().fg(Color::Yellow),
+                                    ),
+                                    Span::from(format!(" {}", reason)),
+                                ]));
+                            }
+
+                            ListItem::new(vec_spans)
+                        })
+                        .collect::<Vec<ListItem>>();
+                    self.cached_output = Cow::Owned(output);
+                }
+                OutputType::FeedCandidates(candidates) => {
+                    let output = candidates
+                        .iter()
+                        .map(move |c| {
+                            let mut vec_spans = vec![];
+                            if let Some(title) = &c.title {
+                                vec_spans.push(Line::from(Span::styled(
+                                    title.clone(),
+                                    Style::default().add_modifier(Modifier::BOLD),
+                                )));
+                            }
+                            vec_spans.push(Line::from(Span::styled(
+                                c.url.clone(),
+                                Style::default().fg(Color::LightBlue),
+                            )));
+                            ListItem::new(vec_spans)
+                        })
+                        .collect::<Vec<ListItem>>();
+                    self.cached_output = Cow::Owned(output);
+                }
+                OutputType::Chapters(chapters) => {
+                    let output = chapters
+                        .iter()
+                        .map(|c| {
+                            ListItem::new(Line::from(vec![
+                                Span::styled(
+                                    format!("{: >8}", DurationWrapper::new(c.start).to_string()),
+                                    Style::default().fg(Color::LightGreen),
+                                ),
+                                Span::from("   "),
+                                Span::styled(
+                                    c.title.clone(),
+                                    Style::default().add_modifier(Modifier::BOLD),
+                                ),
+                            ]))
+                        })
+                        .collect::<Vec<ListItem>>();
+                    self.cached_output = Cow::Owned(output);
+                }
+                OutputType::EpisodeDetails(details) => {
+                    let lines = build_episode_detail_lines(details);
+                    self.cached_output = Cow::Owned(MinimalisticUiDrawer::build_detail_list_items(
+                        lines,
+                        available_width,
+                    ));
+                }
+                OutputType::PodcastDetails(details) => {
+                    let lines = build_podcast_detail_lines(details);
+                    self.cached_output = Cow::Owned(MinimalisticUiDrawer::build_detail_list_items(
+                        lines,
+                        available_width,
+                    ));
+                }
                 _ => unimplemented!(),
             };
             context.must_invalidate_cache.set(false);
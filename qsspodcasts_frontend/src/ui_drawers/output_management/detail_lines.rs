@@ -0,0 +1,137 @@
+use podcast_management::data_objects::episode_details::EpisodeDetails;
+use podcast_management::data_objects::hashable::Hashable;
+use podcast_management::data_objects::podcast_details::PodcastDetails;
+use podcast_player::duration_wrapper::DurationWrapper;
+
+/// Line of a detail pane, each of them being an element of the output list so that the pane can be scrolled
+pub(crate) enum DetailLine {
+    Title(String),
+    Field(&'static str, String),
+    Section(&'static str),
+    Text(String),
+    Link(String),
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "kB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Splits a text in lines, keeping a single blank line between paragraphs
+fn build_text_lines(text: &str) -> Vec<DetailLine> {
+    let mut lines: Vec<DetailLine> = vec![];
+    let mut previous_is_blank = true;
+    for line in text.trim().lines().map(|l| l.trim_end()) {
+        if line.is_empty() && previous_is_blank {
+            continue;
+        }
+        previous_is_blank = line.is_empty();
+        lines.push(DetailLine::Text(line.to_owned()));
+    }
+    lines
+}
+
+pub(crate) fn build_episode_detail_lines(details: &EpisodeDetails) -> Vec<DetailLine> {
+    let episode = &details.episode;
+    let mut lines = vec![
+        DetailLine::Title(episode.title.clone()),
+        DetailLine::Field("Hash", episode.hash()),
+        DetailLine::Field("Podcast", episode.podcast_name.clone()),
+        DetailLine::Field("Author", episode.author.clone()),
+        DetailLine::Field(
+            "Published",
+            episode.pub_date.format("%d/%m/%Y %H:%M").to_string(),
+        ),
+    ];
+    if let Some(duration) = episode.duration.and_then(|d| d.to_std().ok()) {
+        lines.push(DetailLine::Field(
+            "Duration",
+            DurationWrapper::new(duration).to_string(),
+        ));
+    }
+    if !episode.categories.is_empty() {
+        let categories = episode
+            .categories
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<&str>>();
+        lines.push(DetailLine::Field("Categories", categories.join(", ")));
+    }
+    let audio_file = format!(
+        "{}, {}",
+        episode.enclosure_type.as_deref().unwrap_or("unknown type"),
+        episode
+            .enclosure_length
+            .filter(|l| *l != 0)
+            .map(format_size)
+            .unwrap_or("unknown size".to_owned())
+    );
+    lines.push(DetailLine::Field("Audio file", audio_file));
+    let downloaded = if details.is_downloaded { "Yes" } else { "No" };
+    lines.push(DetailLine::Field("Downloaded", downloaded.to_owned()));
+    let progression = match (details.is_finished, details.progression) {
+        (true, _) => "Finished".to_owned(),
+        (false, Some(p)) => format!("Stopped at {}", DurationWrapper::new(p)),
+        (false, None) => "Not started".to_owned(),
+    };
+    lines.push(DetailLine::Field("Progression", progression));
+    if !episode.link.is_empty() {
+        lines.push(DetailLine::Field("Web page", episode.link.clone()));
+    }
+
+    lines.push(DetailLine::Section("Description"));
+    lines.extend(build_text_lines(&episode.description));
+    if !episode.links.is_empty() {
+        lines.push(DetailLine::Section("Links"));
+        lines.extend(episode.links.iter().map(|l| DetailLine::Link(l.clone())));
+    }
+    lines
+}
+
+pub(crate) fn build_podcast_detail_lines(details: &PodcastDetails) -> Vec<DetailLine> {
+    let podcast = &details.podcast;
+    let mut lines = vec![
+        DetailLine::Title(podcast.title.clone()),
+        DetailLine::Field("Hash", podcast.hash()),
+    ];
+    if !podcast.link.is_empty() {
+        lines.push(DetailLine::Field("Web page", podcast.link.clone()));
+    }
+    lines.push(DetailLine::Field("Feed", podcast.feed_url.clone()));
+    if podcast.is_stale {
+        lines.push(DetailLine::Field(
+            "Feed status",
+            "Unreachable, episodes come from a cached copy".to_owned(),
+        ));
+    }
+    lines.push(DetailLine::Field(
+        "Episodes",
+        format!(
+            "{} ({} downloaded, {} in progress, {} finished)",
+            details.episodes_count,
+            details.downloaded_count,
+            details.in_progress_count,
+            details.finished_count
+        ),
+    ));
+    if podcast.skipped_items_count != 0 {
+        lines.push(DetailLine::Field(
+            "Skipped feed items",
+            podcast.skipped_items_count.to_string(),
+        ));
+    }
+
+    lines.push(DetailLine::Section("Description"));
+    lines.extend(build_text_lines(&podcast.description));
+    lines
+}
@@ -1 +1,2 @@
+pub(crate) mod detail_lines;
 pub mod vec_list_items;
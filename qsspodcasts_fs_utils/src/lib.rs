@@ -4,4 +4,5 @@ pub mod permissions;
 pub mod progression_read_utils;
 pub mod progression_write_utils;
 pub mod read_utils;
+pub mod renaming_utils;
 pub mod write_utils;
@@ -4,6 +4,8 @@ use std::path::Path;
 use std::result::Result;
 
 const PERMISSIONS_MASK: u32 = 0xFFF;
+const GROUP_AND_OTHERS_MASK: u32 = 0o077;
+const OWNER_READ_WRITE: u32 = 0o600;
 
 /// Checks permission of file whose path is given in parameter
 ///
@@ -16,6 +18,17 @@ pub fn are_permissions_fulfilled(path: &Path, permissions: u32) -> Result<bool,
     Ok((tested_metadata.permissions().mode() & PERMISSIONS_MASK) >= permissions)
 }
 
+/// Checks that nobody but the owner of the file whose path is given in parameter can access it
+pub fn is_restricted_to_owner(path: &Path) -> Result<bool, std::io::Error> {
+    let tested_metadata = fs::metadata(path)?;
+    Ok((tested_metadata.permissions().mode() & GROUP_AND_OTHERS_MASK) == 0)
+}
+
+/// Makes the file whose path is given in parameter readable and writable by its owner only
+pub fn restrict_to_owner(path: &Path) -> Result<(), std::io::Error> {
+    fs::set_permissions(path, fs::Permissions::from_mode(OWNER_READ_WRITE))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +79,19 @@ mod tests {
 
         are_permissions_fulfilled(&test_data.file_path, tested_permission).unwrap()
     }
+
+    #[test_case("/tmp/file_path4", 0o600 => true; "True if only the owner has permissions")]
+    #[test_case("/tmp/file_path5", 0o640 => false; "False if the group can read the file")]
+    #[test_case("/tmp/file_path6", 0o604 => false; "False if others can read the file")]
+    fn test_is_restricted_to_owner(file_path: &str, file_permission: u32) -> bool {
+        let test_data = FilePermissionsTestData::new(file_path, file_permission);
+        is_restricted_to_owner(&test_data.file_path).unwrap()
+    }
+
+    #[test]
+    fn test_restrict_to_owner() {
+        let test_data = FilePermissionsTestData::new("/tmp/file_path7", 0o644);
+        restrict_to_owner(&test_data.file_path).unwrap();
+        assert!(is_restricted_to_owner(&test_data.file_path).unwrap());
+    }
 }
@@ -12,6 +12,7 @@ pub async fn read_progression_in_file(path: PathBuf) -> Option<std::time::Durati
         .await
         .expect("Opening of file containing progression failed");
     let mut file_reader = BufReader::new(file);
-    let duration_s = file_reader.read_u64().await.unwrap();
+    // Truncated files do not hold any progression
+    let duration_s = file_reader.read_u64().await.ok()?;
     Some(Duration::from_secs(duration_s))
 }
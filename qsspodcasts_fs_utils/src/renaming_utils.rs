@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Renames the files of the directory whose name is a key of `new_names` to the associated value.
+///
+/// Files whose new name is already taken are left as is. Returns the new names of the files renamed.
+pub fn rename_files(
+    dir: &Path,
+    new_names: &HashMap<String, String>,
+) -> Result<Vec<String>, io::Error> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut renamed_files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let new_name = match file_name.to_str().and_then(|n| new_names.get(n)) {
+            Some(n) => n,
+            None => continue,
+        };
+        let new_path = dir.join(new_name);
+        if new_path.exists() {
+            continue;
+        }
+        fs::rename(entry.path(), new_path)?;
+        renamed_files.push(new_name.clone());
+    }
+    Ok(renamed_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_files() {
+        let dir = std::env::temp_dir().join("test_rename_files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["old_1", "old_2", "new_2", "unknown"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+        let new_names = HashMap::from([
+            ("old_1".to_owned(), "new_1".to_owned()),
+            ("old_2".to_owned(), "new_2".to_owned()),
+        ]);
+
+        assert_eq!(rename_files(&dir, &new_names).unwrap(), vec!["new_1"]);
+        assert_eq!(fs::read_to_string(dir.join("new_1")).unwrap(), "old_1");
+        // Existing files are not overwritten
+        assert_eq!(fs::read_to_string(dir.join("new_2")).unwrap(), "new_2");
+        assert!(dir.join("old_2").exists());
+        assert!(dir.join("unknown").exists());
+
+        assert!(rename_files(&dir.join("missing"), &new_names)
+            .unwrap()
+            .is_empty());
+        fs::remove_dir_all(dir).expect("Cleanup of test failed");
+    }
+}
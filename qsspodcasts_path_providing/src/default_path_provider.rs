@@ -65,6 +65,55 @@ impl PathProvider for DefaultPathProvider {
         p.push(hash);
         p
     }
+
+    fn feed_cache_dir_name(&self) -> &'static str {
+        "feed_cache"
+    }
+    fn feed_cache_dir_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.feed_cache_dir_name());
+        p
+    }
+    fn subscriptions_file_name(&self) -> &'static str {
+        "subscriptions.json"
+    }
+    fn subscriptions_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.subscriptions_file_name());
+        p
+    }
+    fn credentials_file_name(&self) -> &'static str {
+        "credentials.json"
+    }
+    fn credentials_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.credentials_file_name());
+        p
+    }
+    fn seen_episodes_file_name(&self) -> &'static str {
+        "seen_episodes"
+    }
+    fn seen_episodes_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.seen_episodes_file_name());
+        p
+    }
+    fn queue_file_name(&self) -> &'static str {
+        "queue"
+    }
+    fn queue_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.queue_file_name());
+        p
+    }
+    fn playlists_file_name(&self) -> &'static str {
+        "playlists"
+    }
+    fn playlists_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.playlists_file_name());
+        p
+    }
 }
 
 unsafe impl Send for DefaultPathProvider {}
@@ -78,6 +78,55 @@ impl PathProvider for DummyPathProvider {
         p.push(hash);
         p
     }
+
+    fn feed_cache_dir_name(&self) -> &'static str {
+        "feed_cache"
+    }
+    fn feed_cache_dir_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.feed_cache_dir_name());
+        p
+    }
+    fn subscriptions_file_name(&self) -> &'static str {
+        "test_subscriptions.json"
+    }
+    fn subscriptions_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.subscriptions_file_name());
+        p
+    }
+    fn credentials_file_name(&self) -> &'static str {
+        "test_credentials.json"
+    }
+    fn credentials_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.credentials_file_name());
+        p
+    }
+    fn seen_episodes_file_name(&self) -> &'static str {
+        "test_seen_episodes"
+    }
+    fn seen_episodes_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.seen_episodes_file_name());
+        p
+    }
+    fn queue_file_name(&self) -> &'static str {
+        "test_queue"
+    }
+    fn queue_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.queue_file_name());
+        p
+    }
+    fn playlists_file_name(&self) -> &'static str {
+        "test_playlists"
+    }
+    fn playlists_file_path(&self) -> PathBuf {
+        let mut p = self.app_dir_path();
+        p.push(self.playlists_file_name());
+        p
+    }
 }
 unsafe impl Send for DummyPathProvider {}
 unsafe impl Sync for DummyPathProvider {}
@@ -14,6 +14,18 @@ pub trait PathProvider {
     fn finished_podcasts_dir_name(&self) -> &'static str;
     fn finished_podcasts_dir_path(&self) -> PathBuf;
     fn compute_finished_podcast_file_path(&self, hash: &str) -> PathBuf;
+    fn feed_cache_dir_name(&self) -> &'static str;
+    fn feed_cache_dir_path(&self) -> PathBuf;
+    fn subscriptions_file_name(&self) -> &'static str;
+    fn subscriptions_file_path(&self) -> PathBuf;
+    fn credentials_file_name(&self) -> &'static str;
+    fn credentials_file_path(&self) -> PathBuf;
+    fn seen_episodes_file_name(&self) -> &'static str;
+    fn seen_episodes_file_path(&self) -> PathBuf;
+    fn queue_file_name(&self) -> &'static str;
+    fn queue_file_path(&self) -> PathBuf;
+    fn playlists_file_name(&self) -> &'static str;
+    fn playlists_file_path(&self) -> PathBuf;
 
     fn compute_episode_path(&self, episode: &PodcastEpisode) -> PathBuf {
         let mut download_dir: PathBuf = self.download_dir_path();
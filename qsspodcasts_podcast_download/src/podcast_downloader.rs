@@ -1,35 +1,49 @@
+use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use bytes::Bytes;
 use log::{debug, info};
-use reqwest;
+use reqwest::{self, Url};
 
 use fs_utils::write_utils::write_bytes_in_file;
 
 use podcast_management::data_objects::podcast_episode::PodcastEpisode;
+use rss_management::http_settings::HttpClient;
+use rss_management::local_storage::feed_credentials::FeedCredentials;
+use rss_management::rss_feed_reading::utils::{local_file_path, send_following_redirects};
 
 use path_providing::path_provider::PathProvider;
 
 pub struct PodcastDownloader {
-    client: reqwest::Client,
+    http_client: HttpClient,
     path_provider: Arc<dyn PathProvider + Send + Sync>,
 }
 
 impl PodcastDownloader {
     pub fn new(path_provider: Arc<dyn PathProvider + Send + Sync>) -> PodcastDownloader {
         PodcastDownloader {
-            client: reqwest::Client::new(),
+            http_client: HttpClient::default(),
             path_provider,
         }
     }
 
+    pub fn set_http_client(&mut self, http_client: HttpClient) {
+        self.http_client = http_client;
+    }
+
+    /// Downloads the audio file of the episode, authenticating with the credentials of its feed if there are some.
+    ///
+    /// The credentials are only sent to the origin of the feed, and not to the hosts the audio file is served from
+    /// or redirected to.
+    ///
+    /// Audio files referenced by a `file://` URL are copied instead.
     pub async fn download_episode(
         &self,
         episode: &PodcastEpisode,
+        credentials: Option<&FeedCredentials>,
     ) -> Result<PathBuf, std::io::Error> {
         let url = &episode.url;
-        let request = self.client.get(url);
         let output_path = self.path_provider.compute_episode_path(episode);
 
         if output_path.exists() {
@@ -37,17 +51,45 @@ impl PodcastDownloader {
             return Ok(output_path);
         }
 
+        if let Some(source_path) = local_file_path(url) {
+            info!(
+                "Copying local podcast episode {episode_title}",
+                episode_title = episode.title
+            );
+            fs::copy(source_path, &output_path)?;
+            return Ok(output_path);
+        }
+
+        let feed_url = Url::parse(&episode.feed_url).ok();
+        let build_request = |request_url: &Url| {
+            let request = self
+                .http_client
+                .manual_redirect_client()
+                .get(request_url.clone());
+            match (credentials, &feed_url) {
+                (Some(c), Some(feed_url)) => c.apply_on_origin(request, feed_url, request_url),
+                _ => request,
+            }
+        };
+
         info!(
             "Downloading podcast episode {episode_title}",
             episode_title = episode.title
         );
 
-        let response: reqwest::Response = match request.send().await {
+        let response: reqwest::Response =
+            send_following_redirects(url, build_request, &self.http_client, true, &mut vec![])
+                .await
+                .map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::TimedOut, "Response reception failed")
+                })?;
+        // Error pages must not be saved as the audio file
+        let response = match response.error_for_status() {
             Ok(r) => r,
-            Err(_) => {
+            Err(e) => {
                 return Err(std::io::Error::new(
-                    std::io::ErrorKind::TimedOut,
-                    "Response reception failed",
+                    std::io::ErrorKind::Other,
+                    format!("Download of episode failed : {}", e),
                 ))
             }
         };
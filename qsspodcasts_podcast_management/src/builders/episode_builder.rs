@@ -4,11 +4,65 @@ use rss;
 pub struct EpisodeBuilder {}
 
 impl EpisodeBuilder {
+    /// Builds an episode from an item, or returns the reason why it cannot be
     pub fn build(&self, item: &rss::Item, podcast_name: &str) -> Result<PodcastEpisode, String> {
-        if let Some(mut episode) = PodcastEpisode::from_item(item) {
-            episode.set_podcast_name(podcast_name);
-            return Ok(episode);
+        let mut episode = PodcastEpisode::from_item(item)?;
+        episode.set_podcast_name(podcast_name);
+        Ok(episode)
+    }
+
+    pub fn build_from_entry(
+        &self,
+        entry: &atom_syndication::Entry,
+        podcast_name: &str,
+    ) -> Result<PodcastEpisode, String> {
+        let mut episode = PodcastEpisode::from_entry(entry)?;
+        episode.set_podcast_name(podcast_name);
+        Ok(episode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn build_item(guid: Option<&str>, pub_date: Option<&str>, with_enclosure: bool) -> rss::Item {
+        rss::Item {
+            title: Some("Episode".to_owned()),
+            link: Some("https://www.example.com/episode".to_owned()),
+            description: Some("Description".to_owned()),
+            guid: guid.map(|g| rss::Guid {
+                value: g.to_owned(),
+                permalink: false,
+            }),
+            pub_date: pub_date.map(|d| d.to_owned()),
+            enclosure: if with_enclosure {
+                Some(rss::Enclosure {
+                    url: "https://www.example.com/episode.mp3".to_owned(),
+                    length: "0".to_owned(),
+                    mime_type: "audio/mpeg".to_owned(),
+                })
+            } else {
+                None
+            },
+            ..Default::default()
         }
-        Err("Episode could not be built".to_owned())
+    }
+
+    #[test_case(Some("1"), Some("Wed, 01 Mar 2023 10:00:00 +0000"), true => Ok(()); "Valid item")]
+    #[test_case(Some("1"), Some("Wed, 01 Mar 2023 10:00:00 +0000"), false => Err("Missing enclosure".to_owned()); "Missing enclosure")]
+    #[test_case(None, None, true => Ok(()); "Missing guid and date")]
+    #[test_case(Some("1"), Some("yesterday"), true => Err("Invalid publication date \"yesterday\"".to_owned()); "Invalid date")]
+    fn test_build(
+        guid: Option<&str>,
+        pub_date: Option<&str>,
+        with_enclosure: bool,
+    ) -> Result<(), String> {
+        let builder = EpisodeBuilder {};
+        builder
+            .build(&build_item(guid, pub_date, with_enclosure), "Podcast")
+            .map(|_| ())
     }
 }
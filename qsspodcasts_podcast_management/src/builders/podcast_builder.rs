@@ -14,13 +14,26 @@ impl PodcastBuilder {
     }
 
     pub fn build(&self, channel: &rss::Channel) -> Podcast {
-        let episodes = channel
-            .items()
-            .iter()
-            .map(|i| self.episode_builder.build(i, &channel.title))
-            .filter_map(|p| p.ok())
-            .collect::<Vec<PodcastEpisode>>();
-        Podcast::new(
+        self.build_with_report(channel).0
+    }
+
+    /// Builds the podcast, and returns the name of the items that could not be turned into episodes along with the reason why
+    pub fn build_with_report(&self, channel: &rss::Channel) -> (Podcast, Vec<(String, String)>) {
+        let mut episodes: Vec<PodcastEpisode> = vec![];
+        let mut skipped_items: Vec<(String, String)> = vec![];
+        for item in channel.items() {
+            match self.episode_builder.build(item, &channel.title) {
+                Ok(e) => episodes.push(e),
+                Err(reason) => {
+                    let name = item
+                        .title()
+                        .or(item.guid().map(|g| g.value()))
+                        .unwrap_or("Untitled item");
+                    skipped_items.push((name.to_owned(), reason));
+                }
+            }
+        }
+        let mut podcast = Podcast::new(
             &channel.title,
             &channel.link,
             &channel.description,
@@ -28,7 +41,52 @@ impl PodcastBuilder {
             channel.pub_date.clone(),
             channel.image.clone(),
             episodes,
-        )
+        );
+        podcast.skipped_items_count = skipped_items.len();
+        (podcast, skipped_items)
+    }
+
+    pub fn build_from_atom(&self, feed: &atom_syndication::Feed) -> Podcast {
+        self.build_from_atom_with_report(feed).0
+    }
+
+    /// Same as `build_with_report`, for Atom feeds
+    pub fn build_from_atom_with_report(
+        &self,
+        feed: &atom_syndication::Feed,
+    ) -> (Podcast, Vec<(String, String)>) {
+        let title = feed.title().as_str();
+        let mut episodes: Vec<PodcastEpisode> = vec![];
+        let mut skipped_items: Vec<(String, String)> = vec![];
+        for entry in feed.entries() {
+            match self.episode_builder.build_from_entry(entry, title) {
+                Ok(e) => episodes.push(e),
+                Err(reason) => skipped_items.push((entry.title().to_string(), reason)),
+            }
+        }
+        let link = feed
+            .links()
+            .iter()
+            .find(|l| l.rel() == "alternate")
+            .map(|l| l.href())
+            .unwrap_or("");
+        let image = feed.logo().or(feed.icon()).map(|url| rss::Image {
+            url: url.to_owned(),
+            title: title.to_owned(),
+            link: link.to_owned(),
+            ..Default::default()
+        });
+        let mut podcast = Podcast::new(
+            title,
+            link,
+            feed.subtitle().map(|s| s.as_str()).unwrap_or(""),
+            feed.rights().map(|r| r.as_str().to_owned()),
+            Some(feed.updated().to_rfc2822()),
+            image,
+            episodes,
+        );
+        podcast.skipped_items_count = skipped_items.len();
+        (podcast, skipped_items)
     }
 }
 
@@ -37,3 +95,50 @@ impl Default for PodcastBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_from_atom() {
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Atom show</title>
+  <subtitle>A show published as Atom</subtitle>
+  <link rel="alternate" href="https://www.example.com"/>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2023-01-02T10:00:00Z</updated>
+  <entry>
+    <title>First episode</title>
+    <link rel="alternate" href="https://www.example.com/1"/>
+    <link rel="enclosure" type="audio/mpeg" href="https://www.example.com/1.mp3"/>
+    <id>urn:uuid:1</id>
+    <updated>2023-01-02T10:00:00Z</updated>
+    <summary>Episode with an enclosure</summary>
+  </entry>
+  <entry>
+    <title>Blog post</title>
+    <link rel="alternate" href="https://www.example.com/post"/>
+    <id>urn:uuid:2</id>
+    <updated>2023-01-01T10:00:00Z</updated>
+    <summary>Entry without enclosure</summary>
+  </entry>
+</feed>"#;
+        let feed = atom_syndication::Feed::read_from(content.as_bytes()).unwrap();
+        let (podcast, skipped_items) = PodcastBuilder::new().build_from_atom_with_report(&feed);
+
+        assert_eq!(
+            skipped_items,
+            vec![("Blog post".to_owned(), "Missing enclosure".to_owned())]
+        );
+        assert_eq!(podcast.skipped_items_count, 1);
+        assert_eq!(podcast.title, "Atom show");
+        assert_eq!(podcast.link, "https://www.example.com");
+        assert_eq!(podcast.episodes.len(), 1);
+        let episode = &podcast.episodes[0];
+        assert_eq!(episode.url, "https://www.example.com/1.mp3");
+        assert_eq!(episode.guid.value, "urn:uuid:1");
+        assert_eq!(episode.podcast_name, "Atom show");
+    }
+}
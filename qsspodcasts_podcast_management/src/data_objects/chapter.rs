@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+/// Going to the previous chapter from further than this in the current one goes back to its beginning instead
+const RESTART_CHAPTER_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// Part of an episode, as announced by its audio file or by its feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub title: String,
+    /// Position of the beginning of the chapter in the episode
+    pub start: Duration,
+}
+
+impl Chapter {
+    pub fn new(title: &str, start: Duration) -> Chapter {
+        Chapter {
+            title: title.to_owned(),
+            start,
+        }
+    }
+}
+
+/// Returns the index of the chapter being played at the given position, the chapters being sorted by start
+pub fn find_chapter_index(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start <= position)
+}
+
+pub fn find_next_chapter(chapters: &[Chapter], position: Duration) -> Option<&Chapter> {
+    let next_index = find_chapter_index(chapters, position).map_or(0, |i| i + 1);
+    chapters.get(next_index)
+}
+
+/// Returns the chapter before the current one, or the current one if it started more than a few seconds ago
+pub fn find_previous_chapter(chapters: &[Chapter], position: Duration) -> Option<&Chapter> {
+    let index = find_chapter_index(chapters, position)?;
+    if index == 0 || position - chapters[index].start >= RESTART_CHAPTER_THRESHOLD {
+        Some(&chapters[index])
+    } else {
+        Some(&chapters[index - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn build_chapters() -> Vec<Chapter> {
+        vec![
+            Chapter::new("Introduction", Duration::from_secs(10)),
+            Chapter::new("Interview", Duration::from_secs(60)),
+            Chapter::new("Conclusion", Duration::from_secs(600)),
+        ]
+    }
+
+    #[test_case(5 => None; "Before the first chapter")]
+    #[test_case(60 => Some(1); "Beginning of a chapter")]
+    #[test_case(3000 => Some(2); "Last chapter")]
+    fn test_find_chapter_index(position: u64) -> Option<usize> {
+        find_chapter_index(&build_chapters(), Duration::from_secs(position))
+    }
+
+    #[test_case(5 => Some("Introduction".to_owned()); "Before the first chapter")]
+    #[test_case(100 => Some("Conclusion".to_owned()); "Middle of a chapter")]
+    #[test_case(600 => None; "Last chapter")]
+    fn test_find_next_chapter(position: u64) -> Option<String> {
+        find_next_chapter(&build_chapters(), Duration::from_secs(position)).map(|c| c.title.clone())
+    }
+
+    #[test_case(5 => None; "Before the first chapter")]
+    #[test_case(12 => Some("Introduction".to_owned()); "First chapter")]
+    #[test_case(61 => Some("Introduction".to_owned()); "Beginning of a chapter")]
+    #[test_case(100 => Some("Interview".to_owned()); "Middle of a chapter")]
+    fn test_find_previous_chapter(position: u64) -> Option<String> {
+        find_previous_chapter(&build_chapters(), Duration::from_secs(position))
+            .map(|c| c.title.clone())
+    }
+}
@@ -0,0 +1,13 @@
+use std::time::Duration;
+
+use super::podcast_episode::PodcastEpisode;
+
+/// Everything known about an episode, along with its state on this computer
+#[derive(Debug, Clone)]
+pub struct EpisodeDetails {
+    pub episode: PodcastEpisode,
+    pub is_downloaded: bool,
+    pub is_finished: bool,
+    /// Position the listening was stopped at, if the episode was started
+    pub progression: Option<Duration>,
+}
@@ -1,4 +1,7 @@
+pub mod chapter;
+pub mod episode_details;
 pub mod hashable;
 pub mod podcast;
+pub mod podcast_details;
 pub mod podcast_episode;
 pub mod podcast_state;
@@ -9,6 +9,12 @@ use sha1::{Digest, Sha1};
 pub struct Podcast {
     pub title: String,
     pub link: String,
+    /// URL of the feed the podcast was built from
+    pub feed_url: String,
+    /// Set if the podcast was built from a cached copy of its feed, which may be outdated
+    pub is_stale: bool,
+    /// Number of items of the feed that could not be turned into episodes
+    pub skipped_items_count: usize,
     pub description: String,
     copyright: Option<String>,
     pub_date: Option<String>,
@@ -30,6 +36,9 @@ impl Podcast {
         Podcast {
             title: title.to_string(),
             link: link.to_string(),
+            feed_url: String::new(),
+            is_stale: false,
+            skipped_items_count: 0,
             description,
             copyright,
             pub_date,
@@ -38,8 +47,15 @@ impl Podcast {
         }
     }
 
+    pub fn set_feed_url(&mut self, url: &str) {
+        self.feed_url = url.to_string();
+        for episode in &mut self.episodes {
+            episode.feed_url = url.to_string();
+        }
+    }
+
     pub fn shallow_copy(&self) -> Podcast {
-        Podcast::new(
+        let mut podcast = Podcast::new(
             &self.title,
             &self.link,
             &self.description,
@@ -47,7 +63,11 @@ impl Podcast {
             self.pub_date.clone(),
             self.image.clone(),
             vec![],
-        )
+        );
+        podcast.set_feed_url(&self.feed_url);
+        podcast.is_stale = self.is_stale;
+        podcast.skipped_items_count = self.skipped_items_count;
+        podcast
     }
 }
 
@@ -0,0 +1,12 @@
+use super::podcast::Podcast;
+
+/// Summary of a podcast and of the listening of its episodes
+#[derive(Debug, Clone)]
+pub struct PodcastDetails {
+    /// Podcast without its episodes
+    pub podcast: Podcast,
+    pub episodes_count: usize,
+    pub downloaded_count: usize,
+    pub finished_count: usize,
+    pub in_progress_count: usize,
+}
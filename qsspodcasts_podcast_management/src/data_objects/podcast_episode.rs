@@ -7,6 +7,7 @@ use sha1::Digest;
 use sha1::Sha1;
 
 use super::hashable::Hashable;
+use crate::date_parsing::{parse_episode_duration, parse_publication_date};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PodcastEpisode {
@@ -22,9 +23,45 @@ pub struct PodcastEpisode {
     pub url: String,
     pub download_path: Option<String>,
     pub podcast_name: String,
+    /// URL of the feed the episode comes from, set along with the one of its podcast
+    pub feed_url: String,
+    /// Duration announced by the feed, if any
+    pub duration: Option<chrono::Duration>,
+    /// Links found in the HTML show notes, in order of appearance
+    pub links: Vec<String>,
+    /// Size of the audio file in bytes, as announced by the feed
+    pub enclosure_length: Option<u64>,
+    /// MIME type of the audio file, as announced by the feed
+    pub enclosure_type: Option<String>,
+    /// URL of the JSON file describing the chapters of the episode, given by the podcast:chapters tag
+    pub chapters_url: Option<String>,
+}
+
+/// Returns the targets of the hyperlinks of an HTML fragment, without duplicates
+fn extract_links(html: &str) -> Vec<String> {
+    let mut links: Vec<String> = vec![];
+    for fragment in html.split("href=").skip(1) {
+        let quote = match fragment.chars().next() {
+            Some(c @ ('"' | '\'')) => c,
+            _ => continue,
+        };
+        let value = &fragment[1..];
+        let link = match value.find(quote) {
+            Some(end) => value[..end].trim().replace("&amp;", "&"),
+            None => continue,
+        };
+        if (link.starts_with("http://") || link.starts_with("https://")) && !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    links
 }
 
 impl PodcastEpisode {
+    /// Builds an episode, failing if its publication date cannot be understood.
+    ///
+    /// An empty publication date means that it is unknown, in which case the episode is dated from the Unix epoch
+    /// so that it is listed after the others.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         title: &str,
@@ -38,9 +75,21 @@ impl PodcastEpisode {
         content: &str,
         url: &str,
         download_path: &Option<String>,
-    ) -> PodcastEpisode {
+    ) -> Result<PodcastEpisode, String> {
+        let pub_date = if pub_date.trim().is_empty() {
+            DateTime::<chrono::Utc>::UNIX_EPOCH.fixed_offset()
+        } else {
+            parse_publication_date(pub_date)
+                .ok_or_else(|| format!("Invalid publication date \"{}\"", pub_date))?
+        };
+        let mut links = extract_links(description);
+        for link in extract_links(content) {
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
         let description = html2text::from_read(description.as_bytes(), usize::MAX);
-        PodcastEpisode {
+        Ok(PodcastEpisode {
             title: title.to_string(),
             link: link.to_string(),
             description,
@@ -50,7 +99,7 @@ impl PodcastEpisode {
                 permalink: guid.permalink,
                 value: guid.value.to_string(),
             },
-            pub_date: DateTime::parse_from_rfc2822(pub_date).unwrap(),
+            pub_date,
             source: rss::Source {
                 title: Some(
                     source
@@ -65,43 +114,166 @@ impl PodcastEpisode {
             url: url.to_string(),
             download_path: download_path.clone(),
             podcast_name: "".to_string(),
-        }
+            feed_url: String::new(),
+            duration: None,
+            links,
+            enclosure_length: None,
+            enclosure_type: None,
+            chapters_url: None,
+        })
     }
 
-    pub fn from_item(item: &rss::Item) -> Option<PodcastEpisode> {
-        if item.title.is_none()
-            || item.link.is_none()
-            || item.description.is_none()
-            || item.enclosure.is_none()
-        {
-            return None;
-        }
-        Some(PodcastEpisode::new(
-            item.title.as_ref().unwrap(),
-            item.link.as_ref().unwrap(),
-            item.description.as_ref().unwrap(),
-            item.author.as_ref().unwrap_or(&String::from("Unknown")),
+    /// Builds an episode from an RSS item, which only needs an enclosure.
+    ///
+    /// Items without a title are named after their audio file, and the ones without a GUID are identified by
+    /// the URL of their audio file.
+    pub fn from_item(item: &rss::Item) -> Result<PodcastEpisode, String> {
+        let enclosure = item.enclosure.as_ref().ok_or("Missing enclosure")?;
+        let title = match item.title.as_deref() {
+            Some(t) if !t.trim().is_empty() => t,
+            _ => enclosure
+                .url
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or(&enclosure.url),
+        };
+        let mut episode = PodcastEpisode::new(
+            title,
+            item.link.as_deref().unwrap_or(""),
+            item.description.as_deref().unwrap_or(""),
+            item.author.as_deref().unwrap_or("Unknown"),
             item.categories.as_ref(),
-            item.guid.as_ref().unwrap(),
-            item.pub_date.as_ref().unwrap(),
+            item.guid.as_ref().unwrap_or(&Guid::default()),
+            item.pub_date.as_deref().unwrap_or(""),
             item.source.as_ref().unwrap_or(&rss::Source::default()),
-            item.content.as_ref().unwrap_or(&String::from("")),
-            &item.enclosure.as_ref().unwrap().url,
+            item.content.as_deref().unwrap_or(""),
+            &enclosure.url,
+            &None,
+        )?;
+        episode.duration = item
+            .itunes_ext
+            .as_ref()
+            .and_then(|e| e.duration.as_deref())
+            .and_then(parse_episode_duration);
+        episode.enclosure_length = enclosure.length.trim().parse().ok();
+        episode.enclosure_type = Some(enclosure.mime_type.clone()).filter(|t| !t.is_empty());
+        episode.chapters_url = item
+            .extensions
+            .get("podcast")
+            .and_then(|e| e.get("chapters"))
+            .and_then(|c| c.first())
+            .and_then(|c| c.attrs.get("url"))
+            .map(|u| u.to_owned());
+        Ok(episode)
+    }
+
+    pub fn from_entry(entry: &atom_syndication::Entry) -> Result<PodcastEpisode, String> {
+        let enclosure = entry
+            .links()
+            .iter()
+            .find(|l| l.rel() == "enclosure")
+            .ok_or("Missing enclosure")?;
+        let link = entry
+            .links()
+            .iter()
+            .find(|l| l.rel() == "alternate")
+            .map(|l| l.href())
+            .unwrap_or("");
+        let content = entry.content().and_then(|c| c.value()).unwrap_or("");
+        let description = entry.summary().map(|s| s.as_str()).unwrap_or(content);
+        let author = entry
+            .authors()
+            .first()
+            .map(|a| a.name())
+            .unwrap_or("Unknown");
+        let categories = entry
+            .categories()
+            .iter()
+            .map(|c| rss::Category {
+                name: c.term().to_owned(),
+                domain: c.scheme().map(|s| s.to_owned()),
+            })
+            .collect::<Vec<rss::Category>>();
+        let guid = rss::Guid {
+            value: entry.id().to_owned(),
+            permalink: false,
+        };
+        let pub_date = entry.published().unwrap_or(entry.updated()).to_rfc2822();
+
+        let mut episode = PodcastEpisode::new(
+            entry.title().as_str(),
+            link,
+            description,
+            author,
+            &categories,
+            &guid,
+            &pub_date,
+            &rss::Source::default(),
+            content,
+            enclosure.href(),
             &None,
-        ))
+        )?;
+        episode.enclosure_length = enclosure.length().and_then(|l| l.trim().parse().ok());
+        episode.enclosure_type = enclosure.mime_type().map(|t| t.to_owned());
+        Ok(episode)
     }
 
     pub fn set_podcast_name(&mut self, name: &str) {
         self.podcast_name = name.to_string();
     }
 
-    pub fn get_file_name(&self) -> String {
+    /// Returns what identifies the episode in its feed : its GUID, or the URL of its audio file if it has none
+    fn identity(&self) -> &str {
+        if !self.guid.value.trim().is_empty() {
+            &self.guid.value
+        } else if !self.url.is_empty() {
+            &self.url
+        } else {
+            &self.title
+        }
+    }
+
+    fn compute_identity_digest(&self) -> Vec<u8> {
+        let mut hasher = Sha1::new();
+        hasher.update(self.identity().as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Returns the key under which the data of the episode (progression, finished marker, audio file) is stored.
+    ///
+    /// Unlike the title or the show notes, the identity of an episode is not supposed to change, so neither does this key.
+    pub fn persistent_key(&self) -> String {
+        hex::encode(self.compute_identity_digest())
+    }
+
+    /// Returns the hash that identified the episode before persistent keys were introduced.
+    ///
+    /// Only needed to rename the files still named after it.
+    pub fn legacy_hash(&self) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(self.title.as_bytes());
+        hasher.update(self.description.as_bytes());
+        let d: [u8; 3] = TryFrom::try_from(&hasher.finalize()[17..]).unwrap();
+        hex::encode(d)
+    }
+
+    fn build_file_name(&self, key: &str) -> String {
         let mut file_name = self.podcast_name.clone();
         file_name.push('_');
-        file_name.push_str(&self.hash());
+        file_name.push_str(key);
         file_name
     }
 
+    pub fn get_file_name(&self) -> String {
+        self.build_file_name(&self.persistent_key())
+    }
+
+    /// Returns the name the audio file of the episode had before persistent keys were introduced
+    pub fn get_legacy_file_name(&self) -> String {
+        self.build_file_name(&self.legacy_hash())
+    }
+
     pub fn was_published_recently(&self) -> bool {
         let todays_date = chrono::Local::now().date_naive();
         let yesterdays_date = chrono::Local::now()
@@ -129,15 +301,14 @@ impl Default for PodcastEpisode {
             "",
             &None,
         )
+        .expect("An episode without publication date is valid")
     }
 }
 
+/// Short hash displayed to the user, derived from the identity of the episode like its persistent key
 impl Hashable for PodcastEpisode {
     fn hash(&self) -> String {
-        let mut hasher = Sha1::new();
-        hasher.update(self.title.as_bytes());
-        hasher.update(self.description.as_bytes());
-        let d: [u8; 3] = TryFrom::try_from(&hasher.finalize()[17..]).unwrap();
+        let d: [u8; 3] = TryFrom::try_from(&self.compute_identity_digest()[17..]).unwrap();
         let hash: String = hex::encode(d);
         hash
     }
@@ -164,6 +335,7 @@ mod tests {
             "",
             &None,
         )
+        .unwrap()
     }
 
     #[test_case(chrono::Local::now() => true; "Ok if the podcast was published today")]
@@ -174,4 +346,109 @@ mod tests {
         let episode = build_episode_for_date_tests(date);
         episode.was_published_recently()
     }
+
+    fn build_episode(guid: &str, url: &str, description: &str) -> PodcastEpisode {
+        let mut episode = PodcastEpisode {
+            title: "Episode".to_owned(),
+            description: description.to_owned(),
+            url: url.to_owned(),
+            ..Default::default()
+        };
+        episode.guid.value = guid.to_owned();
+        episode
+    }
+
+    #[test_case("guid", "https://www.example.com/1.mp3", "guid", "https://www.example.com/2.mp3" => true; "Same GUID")]
+    #[test_case("guid", "https://www.example.com/1.mp3", "other_guid", "https://www.example.com/1.mp3" => false; "Different GUIDs")]
+    #[test_case("", "https://www.example.com/1.mp3", "", "https://www.example.com/1.mp3" => true; "No GUID, same URL")]
+    #[test_case("", "https://www.example.com/1.mp3", "", "https://www.example.com/2.mp3" => false; "No GUID, different URLs")]
+    fn test_persistent_key(guid_1: &str, url_1: &str, guid_2: &str, url_2: &str) -> bool {
+        // Show notes being edited must not change the identity of the episode
+        let episode_1 = build_episode(guid_1, url_1, "Show notes");
+        let episode_2 = build_episode(guid_2, url_2, "Edited show notes");
+        assert_eq!(
+            episode_1.persistent_key() == episode_2.persistent_key(),
+            episode_1.hash() == episode_2.hash()
+        );
+        episode_1.persistent_key() == episode_2.persistent_key()
+    }
+
+    #[test_case("https://www.example.com/episodes/1.mp3", None, None => Ok(("1.mp3".to_owned(), "https://www.example.com/episodes/1.mp3".to_owned(), 0)); "Bare enclosure")]
+    #[test_case("https://www.example.com/1.mp3", Some("Episode"), Some("2023-01-02T10:00:00Z") => Ok(("Episode".to_owned(), "https://www.example.com/1.mp3".to_owned(), 1672653600)); "RFC 3339 date")]
+    #[test_case("https://www.example.com/1.mp3", Some("Episode"), Some("soon") => Err("Invalid publication date \"soon\"".to_owned()); "Invalid date")]
+    fn test_from_item(
+        url: &str,
+        title: Option<&str>,
+        pub_date: Option<&str>,
+    ) -> Result<(String, String, i64), String> {
+        let item = rss::Item {
+            title: title.map(|t| t.to_owned()),
+            pub_date: pub_date.map(|d| d.to_owned()),
+            enclosure: Some(rss::Enclosure {
+                url: url.to_owned(),
+                length: "0".to_owned(),
+                mime_type: "audio/mpeg".to_owned(),
+            }),
+            ..Default::default()
+        };
+        let episode = PodcastEpisode::from_item(&item)?;
+        // Without GUID, the episode is identified by its audio file
+        assert_eq!(
+            episode.persistent_key(),
+            build_episode("", url, "").persistent_key()
+        );
+        Ok((episode.title, episode.url, episode.pub_date.timestamp()))
+    }
+
+    #[test_case("<p>See <a href=\"https://www.example.com/notes?a=1&amp;b=2\">the notes</a></p>" => vec!["https://www.example.com/notes?a=1&b=2"]; "Double quotes and entities")]
+    #[test_case("<a href='https://a.example.com'>A</a> <a href=\"https://b.example.com\">B</a> <a href='https://a.example.com'>A again</a>" => vec!["https://a.example.com", "https://b.example.com"]; "Single quotes and duplicates")]
+    #[test_case("<a href=\"#chapter-1\">Chapter 1</a> <a href=\"mailto:host@example.com\">Mail</a>" => Vec::<String>::new(); "Anchors and mail addresses")]
+    #[test_case("No link in there, href= alone" => Vec::<String>::new(); "Plain text")]
+    fn test_extract_links(html: &str) -> Vec<String> {
+        extract_links(html)
+    }
+
+    #[test]
+    fn test_from_item_enclosure() {
+        let item = rss::Item {
+            description: Some("<a href=\"https://www.example.com/notes\">Notes</a>".to_owned()),
+            enclosure: Some(rss::Enclosure {
+                url: "https://www.example.com/1.mp3".to_owned(),
+                length: "12345".to_owned(),
+                mime_type: "audio/mpeg".to_owned(),
+            }),
+            ..Default::default()
+        };
+        let episode = PodcastEpisode::from_item(&item).unwrap();
+        assert_eq!(episode.enclosure_length, Some(12345));
+        assert_eq!(episode.enclosure_type.as_deref(), Some("audio/mpeg"));
+        assert_eq!(episode.links, vec!["https://www.example.com/notes"]);
+    }
+
+    #[test]
+    fn test_from_item_chapters() {
+        let feed = r#"<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+            <channel>
+                <item>
+                    <enclosure url="https://www.example.com/1.mp3" length="0" type="audio/mpeg"/>
+                    <podcast:chapters url="https://www.example.com/1.json" type="application/json+chapters"/>
+                </item>
+            </channel>
+        </rss>"#;
+        let channel = rss::Channel::read_from(feed.as_bytes()).unwrap();
+        let episode = PodcastEpisode::from_item(&channel.items[0]).unwrap();
+        assert_eq!(
+            episode.chapters_url.as_deref(),
+            Some("https://www.example.com/1.json")
+        );
+    }
+
+    #[test]
+    fn test_keys_format() {
+        let episode = build_episode("guid", "", "Show notes");
+        assert_eq!(episode.persistent_key().len(), 40);
+        assert!(episode.persistent_key().ends_with(&episode.hash()));
+        assert_eq!(episode.legacy_hash().len(), 6);
+        assert_ne!(episode.get_file_name(), episode.get_legacy_file_name());
+    }
 }
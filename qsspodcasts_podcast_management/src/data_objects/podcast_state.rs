@@ -1,6 +1,51 @@
+use std::time::Duration;
+
+/// State of an episode on this computer, the latest step reached taking precedence
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum PodcastState {
     /// Kinda the default state
     Undefined,
+    /// Not listed by the feeds at the previous launch of the application
+    New,
+    Downloaded,
+    /// Started, with the position the listening was stopped at
+    InProgress(Duration),
+    /// Listened to the end, its audio file still being on the disk
     Finished,
+    /// Listened to the end, its audio file having been deleted since
+    Archived,
+}
+
+impl PodcastState {
+    /// Returns how far in the episode the listening was stopped, in percents of its duration
+    pub fn compute_progression_percentage(&self, duration: Option<chrono::Duration>) -> Option<u8> {
+        let position = match self {
+            PodcastState::InProgress(p) => p,
+            _ => return None,
+        };
+        let duration = duration.and_then(|d| d.to_std().ok())?;
+        if duration.is_zero() {
+            return None;
+        }
+        let percentage = position.as_secs_f64() * 100.0 / duration.as_secs_f64();
+        Some(percentage.min(100.0) as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(PodcastState::InProgress(Duration::from_secs(900)), Some(60) => Some(25); "Quarter of the episode")]
+    #[test_case(PodcastState::InProgress(Duration::from_secs(4000)), Some(60) => Some(100); "Announced duration too short")]
+    #[test_case(PodcastState::InProgress(Duration::from_secs(900)), None => None; "Unknown duration")]
+    #[test_case(PodcastState::Finished, Some(60) => None; "Not in progress")]
+    fn test_compute_progression_percentage(
+        state: PodcastState,
+        minutes: Option<i64>,
+    ) -> Option<u8> {
+        state.compute_progression_percentage(minutes.map(chrono::Duration::minutes))
+    }
 }
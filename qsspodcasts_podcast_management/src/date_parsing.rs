@@ -0,0 +1,153 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime};
+
+/// Formats met in feeds that do not follow RFC 2822, once the day of the week is removed
+const FORMATS_WITH_OFFSET: [&str; 5] = [
+    "%d %B %Y %H:%M:%S %z",
+    "%d %B %Y %H:%M %z",
+    "%Y-%m-%d %H:%M:%S %z",
+    "%Y-%m-%dT%H:%M:%S%z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+];
+
+/// Formats without time zone, the dates being assumed to be in UTC
+const FORMATS_WITHOUT_OFFSET: [&str; 5] = [
+    "%d %B %Y %H:%M:%S",
+    "%d %B %Y %H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+];
+
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%d %B %Y"];
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// Time zone abbreviations that RFC 2822 does not define but that feeds use anyway
+const TIME_ZONES: [(&str, &str); 8] = [
+    ("UTC", "+0000"),
+    ("Z", "+0000"),
+    ("BST", "+0100"),
+    ("CET", "+0100"),
+    ("CEST", "+0200"),
+    ("JST", "+0900"),
+    ("AEST", "+1000"),
+    ("AEDT", "+1100"),
+];
+
+/// Removes the day of the week, which is often wrong or misspelled
+fn strip_weekday(date: &str) -> &str {
+    let (first_token, rest) = match date.split_once(|c: char| c == ',' || c.is_whitespace()) {
+        Some(t) => t,
+        None => return date,
+    };
+    let first_token = first_token.to_lowercase();
+    if first_token.chars().all(|c| c.is_ascii_alphabetic())
+        && WEEKDAYS.iter().any(|d| first_token.starts_with(d))
+    {
+        rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace())
+    } else {
+        date
+    }
+}
+
+fn replace_time_zone_name(date: &str) -> String {
+    if let Some((rest, zone)) = date.rsplit_once(' ') {
+        if let Some((_, offset)) = TIME_ZONES.iter().find(|(name, _)| *name == zone) {
+            return format!("{} {}", rest, offset);
+        }
+    }
+    date.to_owned()
+}
+
+/// Parses the publication date of an episode.
+///
+/// RFC 2822 dates, as required by the RSS specification, are tried first, then RFC 3339 ones, and then
+/// a few formats found in feeds in the wild. Dates without a time zone are assumed to be in UTC.
+pub fn parse_publication_date(date: &str) -> Option<DateTime<FixedOffset>> {
+    let date = date.trim();
+    if let Ok(d) = DateTime::parse_from_rfc2822(date) {
+        return Some(d);
+    }
+    if let Ok(d) = DateTime::parse_from_rfc3339(date) {
+        return Some(d);
+    }
+
+    let date = replace_time_zone_name(strip_weekday(date));
+    if let Ok(d) = DateTime::parse_from_rfc2822(&date) {
+        return Some(d);
+    }
+    if let Some(d) = FORMATS_WITH_OFFSET
+        .iter()
+        .find_map(|f| DateTime::parse_from_str(&date, f).ok())
+    {
+        return Some(d);
+    }
+    if let Some(d) = FORMATS_WITHOUT_OFFSET
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(&date, f).ok())
+    {
+        return Some(d.and_utc().fixed_offset());
+    }
+    DATE_FORMATS
+        .iter()
+        .find_map(|f| NaiveDate::parse_from_str(&date, f).ok())
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc().fixed_offset())
+}
+
+/// Parses the duration of an episode as given by the itunes:duration tag, either a number of seconds or
+/// colon separated hours, minutes and seconds (HH:MM:SS, MM:SS...).
+///
+/// Durations too long to be represented are considered invalid.
+pub fn parse_episode_duration(duration: &str) -> Option<Duration> {
+    let mut seconds: i64 = 0;
+    for component in duration.trim().split(':') {
+        let value = component.trim().parse::<f64>().ok()?;
+        if !value.is_finite() || value < 0.0 {
+            return None;
+        }
+        // Values too big for an i64 saturate, and are then rejected like any other overflow
+        seconds = seconds.checked_mul(60)?.checked_add(value as i64)?;
+    }
+    Duration::try_seconds(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case("Mon, 02 Jan 2023 10:00:00 +0000"; "RFC 2822")]
+    #[test_case("2023-01-02T10:00:00Z"; "RFC 3339")]
+    #[test_case("2023-01-02T11:00:00+01:00"; "RFC 3339 with offset")]
+    #[test_case("Fri, 02 Jan 2023 10:00:00 +0000"; "Wrong day of the week")]
+    #[test_case("Monday, 02 Jan 2023 10:00:00 GMT"; "Full day of the week")]
+    #[test_case("Mon, 2 January 2023 10:00:00 +0000"; "Full month name")]
+    #[test_case("Mon, 02 Jan 2023 11:00:00 CET"; "Unknown time zone name")]
+    #[test_case("02 Jan 2023 10:00:00"; "No time zone")]
+    #[test_case("2023-01-02 10:00:00"; "ISO format without time zone")]
+    #[test_case("  Mon, 02 Jan 2023 10:00 +0000 "; "Surrounding spaces and no seconds")]
+    fn test_parse_publication_date(date: &str) {
+        let expected = DateTime::parse_from_rfc3339("2023-01-02T10:00:00Z").unwrap();
+        assert_eq!(parse_publication_date(date), Some(expected));
+    }
+
+    #[test_case("2023-01-02" => Some("2023-01-02T00:00:00+00:00".to_owned()); "Date only")]
+    #[test_case("yesterday" => None; "Not a date")]
+    #[test_case("" => None; "Empty string")]
+    fn test_parse_partial_publication_date(date: &str) -> Option<String> {
+        parse_publication_date(date).map(|d| d.to_rfc3339())
+    }
+
+    #[test_case("3723" => Some(3723); "Seconds")]
+    #[test_case("1:02:03" => Some(3723); "Hours, minutes and seconds")]
+    #[test_case("62:03" => Some(3723); "Minutes and seconds")]
+    #[test_case("1:02:03.5" => Some(3723); "Fractional seconds")]
+    #[test_case("an hour" => None; "Not a duration")]
+    #[test_case("1e300" => None; "Number of seconds too big for an i64")]
+    #[test_case("99999999999999999" => None; "Number of seconds too big for a duration")]
+    #[test_case("9999999999999999:00:00" => None; "Overflowing hours")]
+    fn test_parse_episode_duration(duration: &str) -> Option<i64> {
+        parse_episode_duration(duration).map(|d| d.num_seconds())
+    }
+}
@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Local};
+
+use crate::data_objects::hashable::Hashable;
+use crate::data_objects::podcast_episode::PodcastEpisode;
+use crate::hash_resolution_error::HashResolutionError;
+use crate::podcast_library::PodcastLibrary;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EpisodeSortKey {
+    /// Newest first
+    #[default]
+    Date,
+    /// Shortest first, episodes of unknown duration last
+    Duration,
+    Title,
+    Podcast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListeningStatus {
+    Finished,
+    Unfinished,
+    /// Started but not finished
+    InProgress,
+}
+
+/// Listening state of the episodes, indexed by their persistent key
+#[derive(Debug, Default)]
+pub struct ListeningStates {
+    pub finished: HashSet<String>,
+    pub in_progress: HashSet<String>,
+}
+
+/// Selection of episodes of the library, along with the order in which they are listed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EpisodeFilter {
+    /// Prefixes of the hashes, or URLs of the feeds, of the podcasts the episodes come from.
+    /// Every podcast is selected if empty.
+    pub podcasts: Vec<String>,
+    pub status: Option<ListeningStatus>,
+    /// Maximum age of the episodes
+    pub since: Option<Duration>,
+    pub sort_key: EpisodeSortKey,
+    pub reverse: bool,
+    pub limit: Option<usize>,
+}
+
+impl EpisodeFilter {
+    fn matches_status(&self, episode: &PodcastEpisode, states: &ListeningStates) -> bool {
+        let key = episode.persistent_key();
+        match self.status {
+            None => true,
+            Some(ListeningStatus::Finished) => states.finished.contains(&key),
+            Some(ListeningStatus::Unfinished) => !states.finished.contains(&key),
+            Some(ListeningStatus::InProgress) => {
+                states.in_progress.contains(&key) && !states.finished.contains(&key)
+            }
+        }
+    }
+
+    fn compare(&self, e1: &PodcastEpisode, e2: &PodcastEpisode) -> Ordering {
+        match self.sort_key {
+            EpisodeSortKey::Date => e2.pub_date.cmp(&e1.pub_date),
+            EpisodeSortKey::Duration => match (e1.duration, e2.duration) {
+                (Some(d1), Some(d2)) => d1.cmp(&d2),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            EpisodeSortKey::Title => e1.title.to_lowercase().cmp(&e2.title.to_lowercase()),
+            EpisodeSortKey::Podcast => e1
+                .podcast_name
+                .to_lowercase()
+                .cmp(&e2.podcast_name.to_lowercase())
+                .then(e2.pub_date.cmp(&e1.pub_date)),
+        }
+    }
+
+    fn apply_at(
+        &self,
+        library: &PodcastLibrary,
+        states: &ListeningStates,
+        now: DateTime<Local>,
+    ) -> Result<Vec<PodcastEpisode>, HashResolutionError> {
+        let mut podcast_hashes = HashSet::new();
+        for podcast in &self.podcasts {
+            match library.podcasts().iter().find(|p| p.feed_url == *podcast) {
+                Some(p) => {
+                    podcast_hashes.insert(p.hash());
+                }
+                // Feeds unsubscribed from match nothing, hashes cannot contain a scheme separator
+                None if podcast.contains("://") => {}
+                None => {
+                    podcast_hashes.insert(library.resolve_podcast_hash(podcast)?);
+                }
+            }
+        }
+        let oldest_date = self.since.map(|s| now - s);
+
+        let mut episodes = library
+            .podcasts()
+            .iter()
+            .filter(|p| self.podcasts.is_empty() || podcast_hashes.contains(&p.hash()))
+            .flat_map(|p| p.episodes.iter())
+            .filter(|e| oldest_date.is_none() || e.pub_date >= oldest_date.unwrap())
+            .filter(|e| self.matches_status(e, states))
+            .cloned()
+            .collect::<Vec<PodcastEpisode>>();
+        episodes.sort_by(|e1, e2| self.compare(e1, e2));
+        if self.reverse {
+            episodes.reverse();
+        }
+        if let Some(limit) = self.limit {
+            episodes.truncate(limit);
+        }
+        Ok(episodes)
+    }
+
+    /// Returns the episodes of the library matching the filter, sorted and limited as requested
+    pub fn apply(
+        &self,
+        library: &PodcastLibrary,
+        states: &ListeningStates,
+    ) -> Result<Vec<PodcastEpisode>, HashResolutionError> {
+        self.apply_at(library, states, Local::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_objects::podcast::Podcast;
+
+    use test_case::test_case;
+
+    fn build_episode(title: &str, pub_date: &str, duration: Option<i64>) -> PodcastEpisode {
+        let mut episode = PodcastEpisode {
+            title: title.to_owned(),
+            duration: duration.map(Duration::minutes),
+            ..Default::default()
+        };
+        episode.guid.value = title.to_owned();
+        if !pub_date.is_empty() {
+            episode.pub_date = DateTime::parse_from_rfc3339(pub_date).unwrap();
+        }
+        episode
+    }
+
+    fn build_library() -> PodcastLibrary {
+        let mut news = Podcast::new(
+            "News",
+            "",
+            "",
+            None,
+            None,
+            None,
+            vec![
+                build_episode("Monday news", "2023-01-02T07:00:00Z", Some(10)),
+                build_episode("Tuesday news", "2023-01-03T07:00:00Z", Some(12)),
+            ],
+        );
+        news.set_feed_url("https://www.example.com/news.xml");
+        let mut interviews = Podcast::new(
+            "Interviews",
+            "",
+            "",
+            None,
+            None,
+            None,
+            vec![
+                build_episode("Long interview", "2022-12-25T07:00:00Z", Some(95)),
+                build_episode("Mystery interview", "2023-01-01T07:00:00Z", None),
+            ],
+        );
+        interviews.set_feed_url("https://www.example.com/interviews.xml");
+        let mut library = PodcastLibrary::new();
+        library.push(vec![news, interviews]);
+        library
+    }
+
+    fn build_states() -> ListeningStates {
+        ListeningStates {
+            finished: HashSet::from([build_episode("Monday news", "", None).persistent_key()]),
+            in_progress: HashSet::from(
+                [build_episode("Long interview", "", None).persistent_key()],
+            ),
+        }
+    }
+
+    #[test_case(EpisodeFilter::default() => vec!["Tuesday news", "Monday news", "Mystery interview", "Long interview"]; "Default filter")]
+    #[test_case(EpisodeFilter { status: Some(ListeningStatus::Unfinished), sort_key: EpisodeSortKey::Duration, ..Default::default() } => vec!["Tuesday news", "Long interview", "Mystery interview"]; "Unfinished, shortest first")]
+    #[test_case(EpisodeFilter { status: Some(ListeningStatus::InProgress), ..Default::default() } => vec!["Long interview"]; "In progress")]
+    #[test_case(EpisodeFilter { since: Some(Duration::days(3)), reverse: true, ..Default::default() } => vec!["Mystery interview", "Monday news", "Tuesday news"]; "Recent episodes, oldest first")]
+    #[test_case(EpisodeFilter { sort_key: EpisodeSortKey::Title, limit: Some(2), ..Default::default() } => vec!["Long interview", "Monday news"]; "Limit")]
+    fn test_apply(filter: EpisodeFilter) -> Vec<String> {
+        let now = DateTime::parse_from_rfc3339("2023-01-03T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Local);
+        filter
+            .apply_at(&build_library(), &build_states(), now)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.title)
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_with_podcast() {
+        let library = build_library();
+        let filter = EpisodeFilter {
+            podcasts: vec![library.podcasts()[1].hash()],
+            ..Default::default()
+        };
+        let episodes = filter.apply(&library, &build_states()).unwrap();
+        assert!(episodes.iter().all(|e| e.title.ends_with("interview")));
+        assert_eq!(episodes.len(), 2);
+
+        let filter = EpisodeFilter {
+            podcasts: vec![
+                library.podcasts()[1].hash(),
+                "https://www.example.com/news.xml".to_owned(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(filter.apply(&library, &build_states()).unwrap().len(), 4);
+
+        let filter = EpisodeFilter {
+            podcasts: vec!["https://www.example.com/moved.xml".to_owned()],
+            ..Default::default()
+        };
+        assert!(filter.apply(&library, &build_states()).unwrap().is_empty());
+
+        let filter = EpisodeFilter {
+            podcasts: vec![library.podcasts()[0].hash(), "abcdef".to_owned()],
+            ..Default::default()
+        };
+        assert!(filter.apply(&library, &build_states()).is_err());
+    }
+}
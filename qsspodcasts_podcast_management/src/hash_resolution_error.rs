@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a hash prefix typed by the user does not designate exactly one object of the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashResolutionError {
+    NotFound(String),
+    /// Prefix, and description of every object it matches
+    Ambiguous(String, Vec<String>),
+}
+
+impl fmt::Display for HashResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashResolutionError::NotFound(prefix) => {
+                write!(f, "Nothing matches hash {}", prefix)
+            }
+            HashResolutionError::Ambiguous(prefix, candidates) => write!(
+                f,
+                "Hash {} is ambiguous, candidates are : {}",
+                prefix,
+                candidates.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for HashResolutionError {}
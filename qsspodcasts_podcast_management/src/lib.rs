@@ -1,3 +1,9 @@
 pub mod builders;
 pub mod data_objects;
+pub mod date_parsing;
+pub mod episode_filter;
+pub mod hash_resolution_error;
+pub mod playback_queue;
 pub mod podcast_library;
+pub mod search_index;
+pub mod smart_playlists;
@@ -0,0 +1,181 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+/// Episodes to play one after another, identified by their persistent key.
+///
+/// The queue is written to its file after every change, one key per line, so that it survives restarts.
+#[derive(Debug)]
+pub struct PlaybackQueue {
+    keys: Vec<String>,
+    file_path: PathBuf,
+}
+
+impl PlaybackQueue {
+    pub fn new(file_path: PathBuf) -> PlaybackQueue {
+        PlaybackQueue {
+            keys: vec![],
+            file_path,
+        }
+    }
+
+    /// Reads the queue stored in the file, which is empty if the file does not exist yet
+    pub fn load(file_path: PathBuf) -> Result<PlaybackQueue, io::Error> {
+        let mut queue = PlaybackQueue::new(file_path);
+        if !queue.file_path.exists() {
+            return Ok(queue);
+        }
+        queue.keys = fs::read_to_string(&queue.file_path)?
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_owned())
+            .collect();
+        Ok(queue)
+    }
+
+    fn save(&self) -> Result<(), io::Error> {
+        let mut content = self.keys.join("\n");
+        content.push('\n');
+        fs::write(&self.file_path, content)
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.iter().any(|k| k == key)
+    }
+
+    /// Adds the episode at the end of the queue, unless it is queued already
+    pub fn push(&mut self, key: &str) -> Result<(), io::Error> {
+        if self.contains(key) {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                "Episode already in the queue",
+            ));
+        }
+        self.keys.push(key.to_owned());
+        self.save()
+    }
+
+    pub fn remove(&mut self, key: &str) -> Result<(), io::Error> {
+        let index = self.find(key)?;
+        self.keys.remove(index);
+        self.save()
+    }
+
+    /// Moves the episode at the given index, the following ones being shifted. Indexes past the end move it last.
+    pub fn move_to(&mut self, key: &str, index: usize) -> Result<(), io::Error> {
+        let current_index = self.find(key)?;
+        let key = self.keys.remove(current_index);
+        let index = index.min(self.keys.len());
+        self.keys.insert(index, key);
+        self.save()
+    }
+
+    /// Puts the keys at the front of the queue, in order, before the ones already queued.
+    ///
+    /// Keys that were already queued are moved, and duplicated keys are dropped.
+    pub fn insert_front(&mut self, keys: Vec<String>) -> Result<(), io::Error> {
+        let mut new_keys: Vec<String> = vec![];
+        for key in keys.into_iter().chain(self.keys.drain(..)) {
+            if !new_keys.contains(&key) {
+                new_keys.push(key);
+            }
+        }
+        self.keys = new_keys;
+        self.save()
+    }
+
+    pub fn clear(&mut self) -> Result<(), io::Error> {
+        self.keys.clear();
+        self.save()
+    }
+
+    /// Removes the first episode of the queue and returns its key
+    pub fn pop_front(&mut self) -> Result<Option<String>, io::Error> {
+        if self.keys.is_empty() {
+            return Ok(None);
+        }
+        let key = self.keys.remove(0);
+        self.save()?;
+        Ok(Some(key))
+    }
+
+    fn find(&self, key: &str) -> Result<usize, io::Error> {
+        self.keys
+            .iter()
+            .position(|k| k == key)
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "Episode not in the queue"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn build_queue(name: &str, keys: &[&str]) -> PlaybackQueue {
+        let mut file_path = std::env::temp_dir();
+        file_path.push(format!("qsspodcasts_test_queue_{}", name));
+        let _ = fs::remove_file(&file_path);
+        let mut queue = PlaybackQueue::new(file_path);
+        for key in keys {
+            queue.push(key).unwrap();
+        }
+        queue
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut queue = build_queue("persistence", &["aaa", "bbb", "ccc"]);
+        queue.remove("bbb").unwrap();
+        assert_eq!(queue.pop_front().unwrap(), Some("aaa".to_owned()));
+
+        let loaded_queue = PlaybackQueue::load(queue.file_path.clone()).unwrap();
+        assert_eq!(loaded_queue.keys(), ["ccc"]);
+
+        queue
+            .insert_front(vec!["ddd".to_owned(), "ccc".to_owned(), "ddd".to_owned()])
+            .unwrap();
+        let loaded_queue = PlaybackQueue::load(queue.file_path.clone()).unwrap();
+        assert_eq!(loaded_queue.keys(), ["ddd", "ccc"]);
+
+        queue.insert_front(vec!["eee".to_owned()]).unwrap();
+        let loaded_queue = PlaybackQueue::load(queue.file_path.clone()).unwrap();
+        assert_eq!(loaded_queue.keys(), ["eee", "ddd", "ccc"]);
+
+        queue.clear().unwrap();
+        let loaded_queue = PlaybackQueue::load(queue.file_path.clone()).unwrap();
+        assert!(loaded_queue.is_empty());
+        fs::remove_file(&queue.file_path).unwrap();
+    }
+
+    #[test]
+    fn test_push_twice() {
+        let mut queue = build_queue("push_twice", &["aaa"]);
+        assert_eq!(
+            queue.push("aaa").unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+        assert_eq!(queue.remove("bbb").unwrap_err().kind(), ErrorKind::NotFound);
+        fs::remove_file(&queue.file_path).unwrap();
+    }
+
+    #[test_case("ccc", 0 => vec!["ccc", "aaa", "bbb"]; "To the front")]
+    #[test_case("aaa", 1 => vec!["bbb", "aaa", "ccc"]; "One step back")]
+    #[test_case("aaa", 10 => vec!["bbb", "ccc", "aaa"]; "Past the end")]
+    fn test_move_to(key: &str, index: usize) -> Vec<String> {
+        let mut queue = build_queue(&format!("move_{}_{}", key, index), &["aaa", "bbb", "ccc"]);
+        queue.move_to(key, index).unwrap();
+        fs::remove_file(&queue.file_path).unwrap();
+        queue.keys().to_vec()
+    }
+}
@@ -1,22 +1,134 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::data_objects::hashable::Hashable;
 use crate::data_objects::podcast::Podcast;
 use crate::data_objects::podcast_episode::PodcastEpisode;
+use crate::hash_resolution_error::HashResolutionError;
+use crate::search_index::{DocumentId, SearchIndex};
+
+/// Length of the hashes displayed to the user
+pub const HASH_LEN: usize = 6;
 
 pub struct PodcastLibrary {
-    pub podcasts: Vec<Podcast>,
+    podcasts: Vec<Podcast>,
+    /// Updated along with the podcasts, which is why they can only be modified through the methods of the library
+    search_index: SearchIndex,
 }
 
 impl PodcastLibrary {
     pub fn new() -> PodcastLibrary {
-        PodcastLibrary { podcasts: vec![] }
+        PodcastLibrary {
+            podcasts: vec![],
+            search_index: SearchIndex::new(),
+        }
+    }
+
+    pub fn podcasts(&self) -> &[Podcast] {
+        &self.podcasts
     }
 
     pub fn clear(&mut self) {
         self.podcasts = vec![];
+        self.search_index.clear();
     }
 
     pub fn push(&mut self, podcasts: impl Into<Vec<Podcast>>) {
-        self.podcasts.append(&mut podcasts.into());
+        let mut podcasts = podcasts.into();
+        for podcast in &podcasts {
+            self.search_index.add_podcast(podcast);
+        }
+        self.podcasts.append(&mut podcasts);
+    }
+
+    /// Returns the episodes matching every word and quoted phrase of the query, the most relevant first
+    pub fn search(&self, query: &str) -> Vec<PodcastEpisode> {
+        let episodes = self
+            .podcasts
+            .iter()
+            .flat_map(|p| {
+                p.episodes
+                    .iter()
+                    .map(move |e| (SearchIndex::document_id(p, e), e))
+            })
+            .collect::<HashMap<DocumentId, &PodcastEpisode>>();
+        self.search_index
+            .search(query)
+            .into_iter()
+            .filter_map(|(id, _)| episodes.get(&id).map(|e| (*e).clone()))
+            .collect()
+    }
+
+    /// Merges a freshly built podcast in the library.
+    ///
+    /// Episodes that are not known yet are added to the matching podcast, and their count is returned.
+    /// Known episodes are left untouched. If the podcast is not in the library yet, it is added as is
+    /// and 0 is returned.
+    pub fn merge(&mut self, podcast: Podcast) -> usize {
+        let existing_podcast = self.podcasts.iter_mut().find(|p| {
+            if podcast.feed_url.is_empty() {
+                p.hash() == podcast.hash()
+            } else {
+                p.feed_url == podcast.feed_url
+            }
+        });
+        let existing_podcast = match existing_podcast {
+            Some(p) => p,
+            None => {
+                self.search_index.add_podcast(&podcast);
+                self.podcasts.push(podcast);
+                return 0;
+            }
+        };
+
+        existing_podcast.is_stale = podcast.is_stale;
+        let known_hashes = existing_podcast
+            .episodes
+            .iter()
+            .map(|e| e.hash())
+            .collect::<HashSet<String>>();
+        let new_episodes = podcast
+            .episodes
+            .into_iter()
+            .filter(|e| !known_hashes.contains(&e.hash()))
+            .collect::<Vec<PodcastEpisode>>();
+        let new_episodes_count = new_episodes.len();
+        existing_podcast.episodes.extend(new_episodes);
+        self.search_index.add_podcast(existing_podcast);
+        new_episodes_count
+    }
+
+    /// Replaces the podcast sharing the feed URL of the given one, keeping its position in the library.
+    ///
+    /// Returns the number of episodes that were not known yet. If the podcast is not in the library yet,
+    /// it is added and 0 is returned.
+    pub fn replace(&mut self, podcast: Podcast) -> usize {
+        let existing_podcast = self
+            .podcasts
+            .iter_mut()
+            .find(|p| p.feed_url == podcast.feed_url);
+        let existing_podcast = match existing_podcast {
+            Some(p) => p,
+            None => {
+                self.search_index.add_podcast(&podcast);
+                self.podcasts.push(podcast);
+                return 0;
+            }
+        };
+
+        let known_hashes = existing_podcast
+            .episodes
+            .iter()
+            .map(|e| e.hash())
+            .collect::<HashSet<String>>();
+        let new_episodes_count = podcast
+            .episodes
+            .iter()
+            .filter(|e| !known_hashes.contains(&e.hash()))
+            .count();
+        self.search_index.remove_podcast(existing_podcast);
+        self.search_index.add_podcast(&podcast);
+        *existing_podcast = podcast;
+        new_episodes_count
     }
 
     pub fn search_episode(&self, hash: &str) -> Option<PodcastEpisode> {
@@ -30,6 +142,116 @@ impl PodcastLibrary {
         None
     }
 
+    /// Returns the episodes whose persistent key is the given one.
+    ///
+    /// There are several of them only when a feed repeats an item, in which case they cannot be told apart,
+    /// sharing their progression and audio file as well.
+    pub fn find_episodes_by_key(&self, key: &str) -> Vec<PodcastEpisode> {
+        self.podcasts
+            .iter()
+            .flat_map(|p| p.episodes.iter())
+            .filter(|e| e.persistent_key() == key)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the episode whose hash starts with the given prefix.
+    ///
+    /// Episodes sharing the same hash can still be told apart by a prefix of their persistent key,
+    /// which is why those are matched as well when the prefix is longer than a hash.
+    pub fn resolve_episode(&self, prefix: &str) -> Result<PodcastEpisode, HashResolutionError> {
+        let prefix = prefix.to_lowercase();
+        let matching_episodes = self
+            .podcasts
+            .iter()
+            .flat_map(|p| p.episodes.iter())
+            .filter(|e| {
+                e.hash().starts_with(&prefix)
+                    || (prefix.len() > HASH_LEN && e.persistent_key().starts_with(&prefix))
+            })
+            .collect::<Vec<&PodcastEpisode>>();
+        match matching_episodes.len() {
+            0 => Err(HashResolutionError::NotFound(prefix)),
+            1 => Ok(matching_episodes[0].clone()),
+            _ => {
+                let hashes_collide = matching_episodes.iter().all(|e| e.hash() == prefix);
+                let candidates = matching_episodes
+                    .iter()
+                    .map(|e| {
+                        let key = if hashes_collide {
+                            e.persistent_key()
+                        } else {
+                            e.hash()
+                        };
+                        format!("{} ({})", key, e.title)
+                    })
+                    .collect();
+                Err(HashResolutionError::Ambiguous(prefix, candidates))
+            }
+        }
+    }
+
+    /// Returns the hash of the podcast whose hash starts with the given prefix
+    pub fn resolve_podcast_hash(&self, prefix: &str) -> Result<String, HashResolutionError> {
+        let prefix = prefix.to_lowercase();
+        let matching_podcasts = self
+            .podcasts
+            .iter()
+            .filter(|p| p.hash().starts_with(&prefix))
+            .collect::<Vec<&Podcast>>();
+        match matching_podcasts.len() {
+            0 => Err(HashResolutionError::NotFound(prefix)),
+            1 => Ok(matching_podcasts[0].hash()),
+            _ => Err(HashResolutionError::Ambiguous(
+                prefix,
+                matching_podcasts
+                    .iter()
+                    .map(|p| format!("{} ({})", p.hash(), p.title))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Returns the hashes shared by several podcasts, or by several episodes, along with the titles of these.
+    pub fn find_hash_collisions(&self) -> Vec<(String, Vec<String>)> {
+        let mut podcast_titles: HashMap<String, Vec<String>> = HashMap::new();
+        let mut episode_titles: HashMap<String, Vec<String>> = HashMap::new();
+        for p in &self.podcasts {
+            podcast_titles
+                .entry(p.hash())
+                .or_default()
+                .push(p.title.clone());
+            for e in &p.episodes {
+                episode_titles
+                    .entry(e.hash())
+                    .or_default()
+                    .push(e.title.clone());
+            }
+        }
+        let mut collisions = podcast_titles
+            .into_iter()
+            .chain(episode_titles)
+            .filter(|(_, titles)| titles.len() > 1)
+            .collect::<Vec<(String, Vec<String>)>>();
+        collisions.sort();
+        collisions
+    }
+
+    /// Points the podcast fetched from `old_url` to `new_url`, and returns its title.
+    ///
+    /// If a podcast is already fetched from `new_url`, the one fetched from `old_url` is a duplicate and is removed.
+    pub fn update_feed_url(&mut self, old_url: &str, new_url: &str) -> Option<String> {
+        let index = self.podcasts.iter().position(|p| p.feed_url == old_url)?;
+        self.search_index.remove_podcast(&self.podcasts[index]);
+        if self.podcasts.iter().any(|p| p.feed_url == new_url) {
+            return Some(self.podcasts.remove(index).title);
+        }
+        let podcast = &mut self.podcasts[index];
+        podcast.set_feed_url(new_url);
+        self.search_index.add_podcast(podcast);
+        Some(podcast.title.clone())
+    }
+
     pub fn search_podcast(&self, hash: &str) -> Option<Podcast> {
         for p in &self.podcasts {
             if p.hash() == hash {
@@ -41,6 +263,9 @@ impl PodcastLibrary {
 
     pub fn delete_podcast(&mut self, hash: &str) -> Result<(), std::io::Error> {
         let prev_len = self.podcasts.len();
+        for podcast in self.podcasts.iter().filter(|p| p.hash() == hash) {
+            self.search_index.remove_podcast(podcast);
+        }
         self.podcasts.retain(|p| p.hash() != hash);
         let new_len = self.podcasts.len();
         if prev_len == new_len {
@@ -62,8 +287,28 @@ impl Default for PodcastLibrary {
 #[cfg(test)]
 mod tests {
     use crate::builders::podcast_builder::PodcastBuilder;
+    use crate::data_objects::hashable::Hashable;
+    use crate::data_objects::podcast::Podcast;
+    use crate::data_objects::podcast_episode::PodcastEpisode;
 
-    use super::PodcastLibrary;
+    use super::{PodcastLibrary, HASH_LEN};
+    use crate::hash_resolution_error::HashResolutionError;
+
+    use test_case::test_case;
+
+    fn build_podcast(episode_titles: &[&str]) -> Podcast {
+        let episodes = episode_titles
+            .iter()
+            .map(|t| PodcastEpisode {
+                title: t.to_string(),
+                description: t.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let mut podcast = Podcast::new("Podcast", "", "", None, None, None, episodes);
+        podcast.set_feed_url("https://www.example.com/feed.xml");
+        podcast
+    }
 
     #[test]
     fn test_push() -> Result<(), String> {
@@ -87,4 +332,169 @@ mod tests {
         assert_eq!(library.podcasts.len(), 0);
         Ok(())
     }
+
+    #[test]
+    fn test_merge() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        assert_eq!(library.merge(build_podcast(&["First"])), 0);
+        assert_eq!(library.podcasts.len(), 1);
+
+        assert_eq!(
+            library.merge(build_podcast(&["First", "Second", "Third"])),
+            2
+        );
+        assert_eq!(library.merge(build_podcast(&["First", "Second"])), 0);
+        assert_eq!(library.podcasts.len(), 1);
+        assert_eq!(library.podcasts[0].episodes.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        assert_eq!(library.replace(build_podcast(&["First", "Second"])), 0);
+        let mut other_podcast = build_podcast(&["Other"]);
+        other_podcast.set_feed_url("https://www.example.com/other.xml");
+        library.push(vec![other_podcast]);
+
+        assert_eq!(library.replace(build_podcast(&["Second", "Third"])), 1);
+        assert_eq!(library.podcasts.len(), 2);
+        assert_eq!(
+            library.podcasts[0]
+                .episodes
+                .iter()
+                .map(|e| e.title.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Second", "Third"]
+        );
+        Ok(())
+    }
+
+    #[test_case("0" => Ok("Second".to_owned()); "Unique prefix")]
+    #[test_case("6D4C" => Ok("Third".to_owned()); "Uppercase prefix")]
+    #[test_case("abcdef" => Err(HashResolutionError::NotFound("abcdef".to_owned())); "Unknown hash")]
+    fn test_resolve_episode(prefix: &str) -> Result<String, HashResolutionError> {
+        let mut library = PodcastLibrary::new();
+        library.push(vec![build_podcast(&["First", "Second", "Third"])]);
+        library.resolve_episode(prefix).map(|e| e.title)
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_episode() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        library.push(vec![build_podcast(&["First", "Second", "Third"])]);
+        let hashes = library.podcasts[0]
+            .episodes
+            .iter()
+            .map(|e| e.hash())
+            .collect::<Vec<String>>();
+        let shortest_unique_prefix = |hash: &String| {
+            (1..=HASH_LEN)
+                .map(|l| &hash[..l])
+                .find(|p| hashes.iter().filter(|h| h.starts_with(p)).count() == 1)
+                .unwrap()
+                .to_owned()
+        };
+        for hash in &hashes {
+            let prefix = shortest_unique_prefix(hash);
+            assert_eq!(library.resolve_episode(&prefix).unwrap().hash(), *hash);
+        }
+
+        // Episodes with the same identity share their hash
+        library.push(vec![build_podcast(&["First"])]);
+        let first_hash = library.podcasts[1].episodes[0].hash();
+        match library.resolve_episode(&first_hash) {
+            Err(HashResolutionError::Ambiguous(prefix, candidates)) => {
+                assert_eq!(prefix, first_hash);
+                assert_eq!(candidates.len(), 2);
+            }
+            _ => return Err("Colliding hash should be ambiguous".to_owned()),
+        }
+        let first_key = library.podcasts[1].episodes[0].persistent_key();
+        assert_eq!(library.find_episodes_by_key(&first_key).len(), 2);
+        assert_eq!(
+            library.find_hash_collisions(),
+            vec![
+                (
+                    library.podcasts[0].hash(),
+                    vec!["Podcast".to_owned(), "Podcast".to_owned()]
+                ),
+                (first_hash, vec!["First".to_owned(), "First".to_owned()])
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_follows_library_changes() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        library.push(vec![build_podcast(&["First", "Second"])]);
+        assert_eq!(library.search("second").len(), 1);
+
+        library.replace(build_podcast(&["First", "Third"]));
+        assert!(library.search("second").is_empty());
+        assert_eq!(library.search("third").len(), 1);
+
+        library.merge(build_podcast(&["Fourth"]));
+        library.update_feed_url(
+            "https://www.example.com/feed.xml",
+            "https://www.example.com/new.xml",
+        );
+        assert_eq!(library.search("fourth").len(), 1);
+        assert_eq!(library.search("first").len(), 1);
+
+        let hash = library.podcasts[0].hash();
+        library.delete_podcast(&hash).unwrap();
+        assert!(library.search("first").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_unknown_podcasts() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        library.merge(build_podcast(&["First"]));
+        assert_eq!(library.search("first").len(), 1);
+
+        let mut other_podcast = build_podcast(&["Second"]);
+        other_podcast.set_feed_url("https://www.example.com/other.xml");
+        library.replace(other_podcast);
+        assert_eq!(library.search("second").len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_feed_url() -> Result<(), String> {
+        let mut library = PodcastLibrary::new();
+        library.push(vec![build_podcast(&["First"])]);
+        assert!(library
+            .update_feed_url(
+                "https://unknown.example.com/feed.xml",
+                "https://www.example.com/new.xml"
+            )
+            .is_none());
+
+        assert_eq!(
+            library.update_feed_url(
+                "https://www.example.com/feed.xml",
+                "https://www.example.com/new.xml"
+            ),
+            Some("Podcast".to_owned())
+        );
+        assert_eq!(
+            library.podcasts[0].feed_url,
+            "https://www.example.com/new.xml"
+        );
+        assert_eq!(
+            library.podcasts[0].episodes[0].feed_url,
+            "https://www.example.com/new.xml"
+        );
+
+        library.push(vec![build_podcast(&["First"])]);
+        library.update_feed_url(
+            "https://www.example.com/feed.xml",
+            "https://www.example.com/new.xml",
+        );
+        assert_eq!(library.podcasts.len(), 1);
+        Ok(())
+    }
 }
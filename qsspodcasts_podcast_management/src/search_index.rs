@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use crate::data_objects::podcast::Podcast;
+use crate::data_objects::podcast_episode::PodcastEpisode;
+
+/// Identifies an episode in the index : URL of the feed of its podcast, and persistent key of the episode
+pub type DocumentId = (String, String);
+
+/// Weights of the fields of an episode, so that matches in titles rank above matches in show notes
+const EPISODE_TITLE_WEIGHT: u32 = 4;
+const PODCAST_TITLE_WEIGHT: u32 = 3;
+const AUTHOR_WEIGHT: u32 = 2;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+/// Splits a text in lowercase words, ignoring punctuation
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Splits a query in clauses, each of them being a sequence of words that must appear in a row.
+///
+/// Words between double quotes form a single clause, an unterminated quote running until the end of the query.
+fn parse_query(query: &str) -> Vec<Vec<String>> {
+    let mut clauses = vec![];
+    for (i, part) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            clauses.push(tokenize(part));
+        } else {
+            clauses.extend(part.split_whitespace().map(tokenize));
+        }
+    }
+    clauses.retain(|c| !c.is_empty());
+    clauses
+}
+
+fn count_occurrences(tokens: &[String], phrase: &[String]) -> u32 {
+    tokens
+        .windows(phrase.len())
+        .filter(|w| *w == phrase)
+        .count() as u32
+}
+
+/// Inverted index over the titles, authors and descriptions of the episodes of a library
+#[derive(Default)]
+pub struct SearchIndex {
+    /// For each word, the weighted number of times it appears in each document
+    postings: HashMap<String, HashMap<DocumentId, u32>>,
+    /// Words of each field of a document along with the weight of the field, needed to match phrases
+    documents: HashMap<DocumentId, Vec<(u32, Vec<String>)>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> SearchIndex {
+        SearchIndex::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.postings.clear();
+        self.documents.clear();
+    }
+
+    pub fn document_id(podcast: &Podcast, episode: &PodcastEpisode) -> DocumentId {
+        (podcast.feed_url.clone(), episode.persistent_key())
+    }
+
+    /// Indexes the episodes of the podcast, replacing the ones that were indexed already
+    pub fn add_podcast(&mut self, podcast: &Podcast) {
+        for episode in &podcast.episodes {
+            self.add_document(SearchIndex::document_id(podcast, episode), episode);
+        }
+    }
+
+    pub fn remove_podcast(&mut self, podcast: &Podcast) {
+        for episode in &podcast.episodes {
+            self.remove_document(&SearchIndex::document_id(podcast, episode));
+        }
+    }
+
+    fn add_document(&mut self, id: DocumentId, episode: &PodcastEpisode) {
+        self.remove_document(&id);
+        let fields = vec![
+            (EPISODE_TITLE_WEIGHT, tokenize(&episode.title)),
+            (PODCAST_TITLE_WEIGHT, tokenize(&episode.podcast_name)),
+            (AUTHOR_WEIGHT, tokenize(&episode.author)),
+            (DESCRIPTION_WEIGHT, tokenize(&episode.description)),
+        ];
+        for (weight, tokens) in &fields {
+            for token in tokens {
+                *self
+                    .postings
+                    .entry(token.clone())
+                    .or_default()
+                    .entry(id.clone())
+                    .or_default() += weight;
+            }
+        }
+        self.documents.insert(id, fields);
+    }
+
+    fn remove_document(&mut self, id: &DocumentId) {
+        let fields = match self.documents.remove(id) {
+            Some(f) => f,
+            None => return,
+        };
+        for token in fields.iter().flat_map(|(_, tokens)| tokens) {
+            if let Some(documents) = self.postings.get_mut(token) {
+                documents.remove(id);
+                if documents.is_empty() {
+                    self.postings.remove(token);
+                }
+            }
+        }
+    }
+
+    /// Returns the weighted number of times the clause appears in each document containing all of its words
+    fn match_clause(&self, clause: &[String]) -> HashMap<DocumentId, u32> {
+        let mut candidates = match self.postings.get(&clause[0]) {
+            Some(documents) => documents.clone(),
+            None => return HashMap::new(),
+        };
+        for token in &clause[1..] {
+            let documents = match self.postings.get(token) {
+                Some(d) => d,
+                None => return HashMap::new(),
+            };
+            candidates.retain(|id, _| documents.contains_key(id));
+        }
+        if clause.len() == 1 {
+            return candidates;
+        }
+
+        candidates
+            .into_keys()
+            .filter_map(|id| {
+                let frequency: u32 = self.documents[&id]
+                    .iter()
+                    .map(|(weight, tokens)| weight * count_occurrences(tokens, clause))
+                    .sum();
+                (frequency != 0).then_some((id, frequency))
+            })
+            .collect()
+    }
+
+    /// Returns the documents matching every word and quoted phrase of the query, the most relevant first.
+    ///
+    /// Documents are scored with the weighted frequency of each clause, multiplied by how rare the clause is.
+    pub fn search(&self, query: &str) -> Vec<(DocumentId, f64)> {
+        let clauses = parse_query(query);
+        let documents_count = self.documents.len() as f64;
+        let mut scores: Option<HashMap<DocumentId, f64>> = None;
+        for clause in &clauses {
+            let matches = self.match_clause(clause);
+            let inverse_document_frequency = (1.0 + documents_count / matches.len() as f64).ln();
+            let clause_scores = matches
+                .into_iter()
+                .map(|(id, frequency)| (id, frequency as f64 * inverse_document_frequency));
+            scores = Some(match scores {
+                None => clause_scores.collect(),
+                Some(mut s) => {
+                    let clause_scores = clause_scores.collect::<HashMap<DocumentId, f64>>();
+                    s.retain(|id, _| clause_scores.contains_key(id));
+                    for (id, score) in s.iter_mut() {
+                        *score += clause_scores[id];
+                    }
+                    s
+                }
+            });
+        }
+
+        let mut results = scores
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<Vec<(DocumentId, f64)>>();
+        results.sort_by(|(_, s1), (_, s2)| s2.total_cmp(s1));
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn build_episode(title: &str, description: &str) -> PodcastEpisode {
+        let mut episode = PodcastEpisode {
+            title: title.to_owned(),
+            description: description.to_owned(),
+            author: "Jane Doe".to_owned(),
+            podcast_name: "Science Hour".to_owned(),
+            ..Default::default()
+        };
+        episode.guid.value = title.to_owned();
+        episode
+    }
+
+    fn build_index() -> SearchIndex {
+        let episodes = vec![
+            build_episode("Black holes", "Where light goes to die"),
+            build_episode("The speed of light", "How fast is light, and why"),
+            build_episode("Holes in the ground", "A black cat explores caves"),
+        ];
+        let podcast = Podcast::new("Science Hour", "", "", None, None, None, episodes);
+        let mut index = SearchIndex::new();
+        index.add_podcast(&podcast);
+        index
+    }
+
+    fn search_titles(index: &SearchIndex, query: &str) -> Vec<String> {
+        index
+            .search(query)
+            .into_iter()
+            .map(|((_, key), _)| {
+                ["Black holes", "The speed of light", "Holes in the ground"]
+                    .iter()
+                    .find(|t| build_episode(t, "").persistent_key() == key)
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test_case("hello \"black hole\" world" => vec![vec!["hello"], vec!["black", "hole"], vec!["world"]]; "Quoted phrase")]
+    #[test_case("\"speed of" => vec![vec!["speed", "of"]]; "Unterminated quote")]
+    #[test_case("  Light-speed! " => vec![vec!["light", "speed"]]; "Punctuation and case")]
+    fn test_parse_query(query: &str) -> Vec<Vec<String>> {
+        parse_query(query)
+    }
+
+    #[test_case("light" => vec!["The speed of light", "Black holes"]; "Title matches rank first")]
+    #[test_case("black holes" => vec!["Black holes", "Holes in the ground"]; "All words must match")]
+    #[test_case("\"black holes\"" => vec!["Black holes"]; "Phrase")]
+    #[test_case("quasar" => Vec::<&str>::new(); "No match")]
+    fn test_search(query: &str) -> Vec<String> {
+        search_titles(&build_index(), query)
+    }
+
+    #[test]
+    fn test_search_podcast_title_and_author() {
+        // Every episode matches, with the same score
+        assert_eq!(build_index().search("SCIENCE jane").len(), 3);
+    }
+
+    #[test]
+    fn test_remove_podcast() {
+        let mut index = build_index();
+        let podcast = Podcast::new(
+            "Science Hour",
+            "",
+            "",
+            None,
+            None,
+            None,
+            vec![build_episode("Black holes", "Where light goes to die")],
+        );
+        index.remove_podcast(&podcast);
+        assert_eq!(search_titles(&index, "black"), vec!["Holes in the ground"]);
+        assert!(!index.postings.contains_key("die"));
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+
+const PODCAST_OPTION: &str = "--podcast";
+
+/// Named playlists whose episodes are selected by rules, stored as the options of the filter they apply
+/// (e.g. : `--unfinished --since 3d --podcast https://www.example.com/feed.xml`) so that they are evaluated again each
+/// time they are used.
+///
+/// Playlists are written to their file after every change, one per line, the name followed by the options.
+#[derive(Debug)]
+pub struct SmartPlaylists {
+    definitions: BTreeMap<String, Vec<String>>,
+    file_path: PathBuf,
+}
+
+impl SmartPlaylists {
+    pub fn new(file_path: PathBuf) -> SmartPlaylists {
+        SmartPlaylists {
+            definitions: BTreeMap::new(),
+            file_path,
+        }
+    }
+
+    /// Reads the playlists stored in the file, there being none if the file does not exist yet
+    pub fn load(file_path: PathBuf) -> Result<SmartPlaylists, io::Error> {
+        let mut playlists = SmartPlaylists::new(file_path);
+        if !playlists.file_path.exists() {
+            return Ok(playlists);
+        }
+        for line in fs::read_to_string(&playlists.file_path)?.lines() {
+            let mut tokens = line.split_whitespace().map(|t| t.to_owned());
+            if let Some(name) = tokens.next() {
+                playlists.definitions.insert(name, tokens.collect());
+            }
+        }
+        Ok(playlists)
+    }
+
+    fn save(&self) -> Result<(), io::Error> {
+        let content = self
+            .definitions
+            .iter()
+            .map(|(name, options)| format!("{} {}\n", name, options.join(" ")))
+            .collect::<String>();
+        fs::write(&self.file_path, content)
+    }
+
+    /// Names of the playlists, in alphabetical order
+    pub fn names(&self) -> Vec<&str> {
+        self.definitions.keys().map(|n| n.as_str()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.definitions.get(name).map(|o| o.as_slice())
+    }
+
+    /// Adds the playlist, replacing the one of the same name if any
+    pub fn insert(&mut self, name: &str, options: Vec<String>) -> Result<(), io::Error> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "Playlist names cannot be empty or contain spaces",
+            ));
+        }
+        self.definitions.insert(name.to_owned(), options);
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), io::Error> {
+        if self.definitions.remove(name).is_none() {
+            return Err(io::Error::new(ErrorKind::NotFound, "Unknown playlist"));
+        }
+        self.save()
+    }
+
+    /// Makes the playlists selecting the podcast of the feed that moved select it on its new URL
+    pub fn replace_podcast(&mut self, old_url: &str, new_url: &str) -> Result<(), io::Error> {
+        let mut changed = false;
+        for options in self.definitions.values_mut() {
+            for i in 1..options.len() {
+                if options[i - 1] == PODCAST_OPTION && options[i] == old_url {
+                    options[i] = new_url.to_owned();
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Ok(());
+        }
+        self.save()
+    }
+
+    /// Stops the playlists from selecting the podcast of the feed, which was unsubscribed from.
+    ///
+    /// Playlists that only selected this podcast would select all of them from now on, so they are removed instead.
+    /// Returns their names.
+    pub fn remove_podcast(&mut self, url: &str) -> Result<Vec<String>, io::Error> {
+        let mut changed = false;
+        let mut removed_playlists = vec![];
+        for (name, options) in self.definitions.iter_mut() {
+            let podcasts_count = count_podcasts(options);
+            let mut i = 0;
+            while i + 1 < options.len() {
+                if options[i] == PODCAST_OPTION && options[i + 1] == url {
+                    options.drain(i..i + 2);
+                    changed = true;
+                } else {
+                    i += 1;
+                }
+            }
+            if podcasts_count != 0 && count_podcasts(options) == 0 {
+                removed_playlists.push(name.clone());
+            }
+        }
+        for name in &removed_playlists {
+            self.definitions.remove(name);
+        }
+        if changed {
+            self.save()?;
+        }
+        Ok(removed_playlists)
+    }
+}
+
+fn count_podcasts(options: &[String]) -> usize {
+    options.iter().filter(|o| *o == PODCAST_OPTION).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_playlists(name: &str) -> SmartPlaylists {
+        let mut file_path = std::env::temp_dir();
+        file_path.push(format!("qsspodcasts_test_playlists_{}", name));
+        let _ = fs::remove_file(&file_path);
+        SmartPlaylists::new(file_path)
+    }
+
+    fn to_options(options: &[&str]) -> Vec<String> {
+        options.iter().map(|o| o.to_string()).collect()
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut playlists = build_playlists("persistence");
+        let news = to_options(&["--unfinished", "--since", "3d", "--podcast", "a1b2c3"]);
+        playlists.insert("news", news.clone()).unwrap();
+        playlists.insert("everything", vec![]).unwrap();
+        playlists
+            .insert("short", to_options(&["--sort", "duration"]))
+            .unwrap();
+        playlists.remove("short").unwrap();
+
+        let loaded_playlists = SmartPlaylists::load(playlists.file_path.clone()).unwrap();
+        assert_eq!(loaded_playlists.names(), vec!["everything", "news"]);
+        assert_eq!(loaded_playlists.get("news"), Some(news.as_slice()));
+        assert_eq!(loaded_playlists.get("everything"), Some([].as_slice()));
+        fs::remove_file(&playlists.file_path).unwrap();
+    }
+
+    #[test]
+    fn test_moved_and_deleted_feeds() {
+        let mut playlists = build_playlists("moved_and_deleted_feeds");
+        let old_url = "https://old.example.com/feed.xml";
+        let other_url = "https://www.example.com/other.xml";
+        playlists
+            .insert("news", to_options(&["--unfinished", "--podcast", old_url]))
+            .unwrap();
+        playlists
+            .insert(
+                "both",
+                to_options(&["--podcast", old_url, "--podcast", other_url]),
+            )
+            .unwrap();
+
+        playlists
+            .replace_podcast(old_url, "https://new.example.com/feed.xml")
+            .unwrap();
+        let loaded_playlists = SmartPlaylists::load(playlists.file_path.clone()).unwrap();
+        assert_eq!(
+            loaded_playlists.get("news"),
+            Some(
+                to_options(&[
+                    "--unfinished",
+                    "--podcast",
+                    "https://new.example.com/feed.xml"
+                ])
+                .as_slice()
+            )
+        );
+
+        let removed_playlists = playlists
+            .remove_podcast("https://new.example.com/feed.xml")
+            .unwrap();
+        assert_eq!(removed_playlists, vec!["news"]);
+        let loaded_playlists = SmartPlaylists::load(playlists.file_path.clone()).unwrap();
+        assert_eq!(loaded_playlists.names(), vec!["both"]);
+        assert_eq!(
+            loaded_playlists.get("both"),
+            Some(to_options(&["--podcast", other_url]).as_slice())
+        );
+        fs::remove_file(&playlists.file_path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_changes() {
+        let mut playlists = build_playlists("invalid_changes");
+        assert_eq!(
+            playlists.insert("my news", vec![]).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            playlists.remove("news").unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        assert!(!playlists.file_path.exists());
+    }
+}
@@ -22,7 +22,7 @@ pub enum ErrorKind {
 /// * Find  way to abstract the wrapping logic to reuse it somewhere else.
 #[derive(Debug)]
 pub struct PlayerError {
-    source: Option<Box<dyn Error>>,
+    source: Option<Box<dyn Error + Send + Sync>>,
     kind: ErrorKind,
 }
 
@@ -30,7 +30,7 @@ impl PlayerError {
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
-    pub fn new(source: Option<Box<dyn Error>>, kind: ErrorKind) -> PlayerError {
+    pub fn new(source: Option<Box<dyn Error + Send + Sync>>, kind: ErrorKind) -> PlayerError {
         PlayerError { source, kind }
     }
 }
@@ -9,7 +9,6 @@ use gstreamer_play::{
     gst::{init, ClockTime},
     Play as GStreamerInnerPlayer, PlaySignalAdapter, PlayVideoRenderer,
 };
-use podcast_management::data_objects::hashable::Hashable;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::spawn;
@@ -84,7 +83,7 @@ impl GStreamerMp3Player {
                         .unwrap()
                         .read()
                         .await
-                        .hash();
+                        .persistent_key();
                     let observers = &locked_player.observers;
 
                     observers.iter().for_each(move |o| {
@@ -178,14 +177,14 @@ impl Mp3Player for GStreamerMp3Player {
     ) -> Result<(), PlayerError> {
         if (episode.is_none() && self.player_state.is_none())
             || (self.player_state.is_some()
-                && episode.as_ref().unwrap().hash()
+                && episode.as_ref().unwrap().persistent_key()
                     == (*self.player_state.as_ref().unwrap())
                         .read()
                         .await
                         .selected_episode
                         .read()
                         .await
-                        .hash())
+                        .persistent_key())
         {
             return Err(PlayerError::new(
                 None,
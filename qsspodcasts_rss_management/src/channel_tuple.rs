@@ -1,3 +1,3 @@
-use rss::Channel;
+use crate::rss_feed_reading::feed::Feed;
 
-pub type ChannelTuple<'a> = (&'a str, Channel);
+pub type ChannelTuple<'a> = (&'a str, Feed);
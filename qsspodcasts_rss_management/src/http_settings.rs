@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use log::warn;
+use reqwest::redirect::Policy;
+use reqwest::{Client, ClientBuilder, Proxy, RequestBuilder, Response, StatusCode};
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_FEED_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_MAX_PARALLEL_FETCHES: usize = 8;
+pub const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Settings shared by the HTTP clients used to download feeds and episodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSettings {
+    /// Maximum duration of the connection to a server
+    pub connect_timeout: Duration,
+    /// Maximum duration of the download of a feed. Episodes are not concerned, given how big they can be
+    pub feed_timeout: Duration,
+    pub user_agent: String,
+    /// Proxy used for both HTTP and HTTPS. If there is none, the proxies set in the environment are used
+    pub proxy: Option<String>,
+    /// Maximum number of feeds downloaded at the same time
+    pub max_parallel_fetches: usize,
+    /// Number of times a request is sent again after a network or server failure
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every following one
+    pub retry_backoff: Duration,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        HttpSettings {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            feed_timeout: Duration::from_secs(DEFAULT_FEED_TIMEOUT_SECS),
+            user_agent: format!("qsspodcasts/{}", env!("CARGO_PKG_VERSION")),
+            proxy: None,
+            max_parallel_fetches: DEFAULT_MAX_PARALLEL_FETCHES,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Returns a client builder configured with the settings, which can be customized further
+    fn client_builder(&self) -> reqwest::Result<ClientBuilder> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .user_agent(&self.user_agent);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        Ok(builder)
+    }
+
+    fn retry_delay(&self, retry: u32) -> Duration {
+        self.retry_backoff * 2u32.saturating_pow(retry)
+    }
+
+    /// Sends the request, and sends it again with an exponential backoff as long as it fails in a way that may be transient
+    pub async fn send_with_retry(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut retry = 0;
+        loop {
+            // Requests with a streamed body cannot be sent again
+            let attempt = match request.try_clone() {
+                Some(r) => r,
+                None => return request.send().await,
+            };
+            let result = attempt.send().await;
+            if retry >= self.max_retries || !is_transient_failure(&result) {
+                return result;
+            }
+            let delay = self.retry_delay(retry);
+            match &result {
+                Ok(response) => warn!(
+                    "Request to {} failed with status {}, retrying in {:?}",
+                    response.url(),
+                    response.status(),
+                    delay
+                ),
+                Err(e) => warn!("Request failed : {}, retrying in {:?}", e, delay),
+            }
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        }
+    }
+}
+
+/// HTTP clients built once from the settings, so that connections are reused from one request to the other.
+///
+/// Clones share the same connection pools.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    settings: HttpSettings,
+    client: Client,
+    manual_redirect_client: Client,
+}
+
+impl HttpClient {
+    pub fn new(settings: HttpSettings) -> reqwest::Result<HttpClient> {
+        Ok(HttpClient {
+            client: settings.client_builder()?.build()?,
+            manual_redirect_client: settings
+                .client_builder()?
+                .redirect(Policy::none())
+                .build()?,
+            settings,
+        })
+    }
+
+    pub fn settings(&self) -> &HttpSettings {
+        &self.settings
+    }
+
+    /// Client following redirections by itself
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Client that does not follow redirections, so that they can be followed by `send_following_redirects`
+    pub fn manual_redirect_client(&self) -> &Client {
+        &self.manual_redirect_client
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        HttpClient::new(HttpSettings::default()).expect("Building of HTTP client failed")
+    }
+}
+
+fn is_transient_failure(result: &reqwest::Result<Response>) -> bool {
+    match result {
+        Ok(response) => {
+            response.status().is_server_error()
+                || response.status() == StatusCode::TOO_MANY_REQUESTS
+        }
+        Err(e) => e.is_timeout() || e.is_connect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(0 => Duration::from_millis(500); "First retry")]
+    #[test_case(1 => Duration::from_secs(1); "Second retry")]
+    #[test_case(3 => Duration::from_secs(4); "Fourth retry")]
+    fn test_retry_delay(retry: u32) -> Duration {
+        let settings = HttpSettings {
+            retry_backoff: Duration::from_millis(500),
+            ..Default::default()
+        };
+        settings.retry_delay(retry)
+    }
+
+    #[test_case(None => true; "No proxy")]
+    #[test_case(Some("http://proxy.example.com:3128") => true; "Valid proxy")]
+    #[test_case(Some("not a proxy") => false; "Invalid proxy")]
+    fn test_build_client(proxy: Option<&str>) -> bool {
+        let settings = HttpSettings {
+            proxy: proxy.map(|p| p.to_owned()),
+            ..Default::default()
+        };
+        HttpClient::new(settings).is_ok()
+    }
+}
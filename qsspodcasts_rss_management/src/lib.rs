@@ -1,5 +1,7 @@
 // Add `pub mod` here, to make them accessible to external crates
 pub mod channel_tuple;
+pub mod http_settings;
 pub mod local_storage;
+pub mod opml;
 pub mod rss_feed_reading;
 pub mod url_storage;
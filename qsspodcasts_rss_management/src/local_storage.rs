@@ -1,2 +1,5 @@
 pub mod application_dir_initializer;
+pub mod credential_store;
+pub mod feed_cache;
+pub mod feed_credentials;
 pub mod rss_provider;
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::result::Result;
 use std::sync::Arc;
 
+use crate::url_storage::json_url_storer::JsonUrlStorer;
 use path_providing::path_provider::PathProvider;
 
 pub struct ApplicationDirInitializer {
@@ -15,12 +16,13 @@ pub struct ApplicationDirInitializer {
 impl ApplicationDirInitializer {
     pub fn is_app_dir_created(&self, path: PathBuf) -> bool {
         Path::new(&path).exists()
-            && Path::new(
-                &(path.to_str().unwrap().to_owned()
-                    + "/"
-                    + self.path_provider.rss_feed_list_file_name()),
-            )
-            .exists()
+            && (path
+                .join(self.path_provider.subscriptions_file_name())
+                .exists()
+                // Subscriptions of older versions are migrated on first read
+                || path
+                    .join(self.path_provider.rss_feed_list_file_name())
+                    .exists())
             && Path::new(
                 &(path.to_str().unwrap().to_owned() + "/" + self.path_provider.download_dir_name()),
             )
@@ -41,15 +43,17 @@ impl ApplicationDirInitializer {
         ApplicationDirInitializer::is_path_valid(&app_dir_path)?;
         fs::create_dir_all(&app_dir_path)?;
 
-        let rss_feed_list_file_path: PathBuf =
-            app_dir_path.join(self.path_provider.rss_feed_list_file_name());
-        fs::File::create(rss_feed_list_file_path)?;
+        let subscriptions_file_path: PathBuf =
+            app_dir_path.join(self.path_provider.subscriptions_file_name());
+        JsonUrlStorer::create_empty_file(&subscriptions_file_path)?;
         let download_dir_path: PathBuf = app_dir_path.join(self.path_provider.download_dir_name());
         let progresses_dir_path = self.path_provider.podcast_progresses_dir_path();
         let finished_podcasts_dir_path = self.path_provider.finished_podcasts_dir_path();
+        let feed_cache_dir_path = self.path_provider.feed_cache_dir_path();
         fs::create_dir_all(download_dir_path)?;
         fs::create_dir_all(progresses_dir_path)?;
         fs::create_dir_all(finished_podcasts_dir_path)?;
+        fs::create_dir_all(feed_cache_dir_path)?;
 
         Ok(())
     }
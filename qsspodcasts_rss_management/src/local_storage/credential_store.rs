@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+
+use fs_utils::permissions::{is_restricted_to_owner, restrict_to_owner};
+use reqwest::header::{HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use super::feed_credentials::FeedCredentials;
+
+const CURRENT_VERSION: u32 = 1;
+const OWNER_READ_WRITE: u32 = 0o600;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CredentialFile {
+    version: u32,
+    credentials: BTreeMap<String, FeedCredentials>,
+}
+
+impl Default for CredentialFile {
+    fn default() -> Self {
+        CredentialFile {
+            version: CURRENT_VERSION,
+            credentials: BTreeMap::new(),
+        }
+    }
+}
+
+/// Stores the credentials of private feeds, indexed by feed URL.
+///
+/// The file is kept apart from the subscriptions, and is never read if users other than its owner can access it.
+pub struct CredentialStore {
+    file_path: PathBuf,
+}
+
+impl CredentialStore {
+    pub fn new(file_path: PathBuf) -> CredentialStore {
+        CredentialStore { file_path }
+    }
+
+    fn read_file(&self) -> Result<CredentialFile, io::Error> {
+        if !self.file_path.exists() {
+            return Ok(CredentialFile::default());
+        }
+        if !is_restricted_to_owner(&self.file_path)? {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "{} can be accessed by other users, restrict its permissions to 600 before using it",
+                    self.file_path.display()
+                ),
+            ));
+        }
+        let content = fs::read_to_string(&self.file_path)?;
+        let file: CredentialFile = serde_json::from_str(&content)?;
+        if file.version != CURRENT_VERSION {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported credential file version {}", file.version),
+            ));
+        }
+        Ok(file)
+    }
+
+    fn write_file(&self, file: &CredentialFile) -> Result<(), io::Error> {
+        let content = serde_json::to_string_pretty(file)?;
+        // The mode only applies when the file is created, hence the explicit restriction afterwards
+        let mut output = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(OWNER_READ_WRITE)
+            .open(&self.file_path)?;
+        restrict_to_owner(&self.file_path)?;
+        output.write_all(content.as_bytes())
+    }
+
+    fn update_credentials(
+        &mut self,
+        url: &str,
+        update: impl FnOnce(&mut FeedCredentials),
+    ) -> Result<(), io::Error> {
+        let mut file = self.read_file()?;
+        update(file.credentials.entry(url.to_owned()).or_default());
+        self.write_file(&file)
+    }
+
+    pub fn get_all_credentials(&self) -> Result<BTreeMap<String, FeedCredentials>, io::Error> {
+        Ok(self.read_file()?.credentials)
+    }
+
+    pub fn get_credentials(&self, url: &str) -> Result<Option<FeedCredentials>, io::Error> {
+        Ok(self.read_file()?.credentials.remove(url))
+    }
+
+    pub fn set_basic_auth(
+        &mut self,
+        url: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<(), io::Error> {
+        self.update_credentials(url, |c| {
+            c.username = Some(username.to_owned());
+            c.password = Some(password.to_owned());
+        })
+    }
+
+    pub fn set_header(&mut self, url: &str, name: &str, value: &str) -> Result<(), io::Error> {
+        if HeaderName::from_bytes(name.as_bytes()).is_err() || HeaderValue::from_str(value).is_err()
+        {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid header {}", name),
+            ));
+        }
+        self.update_credentials(url, |c| {
+            c.headers.insert(name.to_owned(), value.to_owned());
+        })
+    }
+
+    /// Makes the credentials of a feed apply to another URL
+    pub fn move_credentials(&mut self, old_url: &str, new_url: &str) -> Result<(), io::Error> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+        let mut file = self.read_file()?;
+        if let Some(credentials) = file.credentials.remove(old_url) {
+            file.credentials.insert(new_url.to_owned(), credentials);
+            self.write_file(&file)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_credentials(&mut self, url: &str) -> Result<(), io::Error> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+        let mut file = self.read_file()?;
+        if file.credentials.remove(url).is_some() {
+            self.write_file(&file)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::PermissionsExt;
+
+    const URL: &str = "https://www.example.com/private.xml";
+
+    #[test]
+    fn test_store_credentials() {
+        let file_path = PathBuf::from("/tmp/test_store_credentials.json");
+        let mut store = CredentialStore::new(file_path.clone());
+
+        assert!(store.get_credentials(URL).unwrap().is_none());
+        store.set_basic_auth(URL, "user", "password").unwrap();
+        store.set_header(URL, "X-Api-Key", "secret").unwrap();
+        assert!(is_restricted_to_owner(&file_path).unwrap());
+
+        let credentials = store.get_credentials(URL).unwrap().unwrap();
+        assert_eq!(credentials.username.as_deref(), Some("user"));
+        assert_eq!(credentials.password.as_deref(), Some("password"));
+        assert_eq!(credentials.headers["X-Api-Key"], "secret");
+
+        let new_url = "https://new.example.com/private.xml";
+        store.move_credentials(URL, new_url).unwrap();
+        assert!(store.get_credentials(URL).unwrap().is_none());
+        assert_eq!(
+            store.get_credentials(new_url).unwrap().unwrap(),
+            credentials
+        );
+
+        store.delete_credentials(new_url).unwrap();
+        assert!(store.get_credentials(new_url).unwrap().is_none());
+
+        fs::remove_file(file_path).expect("Cleanup of test failed");
+    }
+
+    #[test]
+    fn test_refuse_readable_file() {
+        let file_path = PathBuf::from("/tmp/test_refuse_readable_credentials.json");
+        let mut store = CredentialStore::new(file_path.clone());
+        store.set_basic_auth(URL, "user", "password").unwrap();
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o644))
+            .expect("Initialization of test failed");
+
+        assert_eq!(
+            store.get_credentials(URL).unwrap_err().kind(),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            store
+                .set_header(URL, "X-Api-Key", "secret")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PermissionDenied
+        );
+
+        fs::remove_file(file_path).expect("Cleanup of test failed");
+    }
+
+    #[test]
+    fn test_refuse_invalid_header() {
+        let file_path = PathBuf::from("/tmp/test_refuse_invalid_header.json");
+        let mut store = CredentialStore::new(file_path.clone());
+        assert_eq!(
+            store
+                .set_header(URL, "Invalid Header", "value")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidInput
+        );
+        assert!(!file_path.exists());
+    }
+}
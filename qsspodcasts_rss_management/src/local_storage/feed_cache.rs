@@ -0,0 +1,189 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use reqwest::header::{HeaderMap, ETAG, LAST_MODIFIED};
+use sha1::{Digest, Sha1};
+
+const ETAG_KEY: &str = "etag";
+const LAST_MODIFIED_KEY: &str = "last-modified";
+
+/// HTTP validators sent back by a server along with a feed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeedValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl FeedValidators {
+    pub fn from_headers(headers: &HeaderMap) -> FeedValidators {
+        let read_header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned())
+        };
+        FeedValidators {
+            etag: read_header(ETAG),
+            last_modified: read_header(LAST_MODIFIED),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    fn serialize(&self) -> String {
+        let mut content = String::new();
+        if let Some(etag) = &self.etag {
+            content += &format!("{}: {}\n", ETAG_KEY, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            content += &format!("{}: {}\n", LAST_MODIFIED_KEY, last_modified);
+        }
+        content
+    }
+
+    fn deserialize(content: &str) -> FeedValidators {
+        let mut validators = FeedValidators::default();
+        for line in content.lines() {
+            match line.split_once(": ") {
+                Some((ETAG_KEY, v)) => validators.etag = Some(v.to_owned()),
+                Some((LAST_MODIFIED_KEY, v)) => validators.last_modified = Some(v.to_owned()),
+                _ => {}
+            }
+        }
+        validators
+    }
+}
+
+/// Local copies of downloaded feeds, along with the validators needed to make conditional requests
+#[derive(Clone)]
+pub struct FeedCache {
+    cache_dir: PathBuf,
+}
+
+impl FeedCache {
+    pub fn new(cache_dir: PathBuf) -> FeedCache {
+        FeedCache { cache_dir }
+    }
+
+    fn compute_entry_name(url: &str) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(url.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn content_path(&self, url: &str) -> PathBuf {
+        self.cache_dir
+            .join(format!("{}.xml", FeedCache::compute_entry_name(url)))
+    }
+
+    fn validators_path(&self, url: &str) -> PathBuf {
+        self.cache_dir
+            .join(format!("{}.validators", FeedCache::compute_entry_name(url)))
+    }
+
+    /// Returns the validators of the cached copy of the feed.
+    ///
+    /// Validators are only returned if a cached copy exists, so that a 304 response can always be served.
+    pub fn read_validators(&self, url: &str) -> FeedValidators {
+        if !self.content_path(url).is_file() {
+            return FeedValidators::default();
+        }
+        match fs::read_to_string(self.validators_path(url)) {
+            Ok(content) => FeedValidators::deserialize(&content),
+            Err(_) => FeedValidators::default(),
+        }
+    }
+
+    pub fn read_content(&self, url: &str) -> Result<Vec<u8>, io::Error> {
+        fs::read(self.content_path(url))
+    }
+
+    pub fn store(
+        &self,
+        url: &str,
+        content: &[u8],
+        validators: &FeedValidators,
+    ) -> Result<(), io::Error> {
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(self.content_path(url), content)?;
+        fs::write(self.validators_path(url), validators.serialize())
+    }
+
+    /// Makes the cached copy of a feed available under another URL
+    pub fn move_entry(&self, old_url: &str, new_url: &str) -> Result<(), io::Error> {
+        self.delete(new_url)?;
+        for (old_path, new_path) in [
+            (self.content_path(old_url), self.content_path(new_url)),
+            (self.validators_path(old_url), self.validators_path(new_url)),
+        ] {
+            if old_path.exists() {
+                fs::rename(old_path, new_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn delete(&self, url: &str) -> Result<(), io::Error> {
+        for path in [self.content_path(url), self.validators_path(url)] {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(Some("\"abc\""), Some("Wed, 21 Oct 2015 07:28:00 GMT"); "Both validators")]
+    #[test_case(None, Some("Wed, 21 Oct 2015 07:28:00 GMT"); "Only last modified")]
+    #[test_case(None, None; "No validator")]
+    fn test_validators_serialization(etag: Option<&str>, last_modified: Option<&str>) {
+        let validators = FeedValidators {
+            etag: etag.map(|e| e.to_owned()),
+            last_modified: last_modified.map(|l| l.to_owned()),
+        };
+        assert_eq!(
+            FeedValidators::deserialize(&validators.serialize()),
+            validators
+        );
+    }
+
+    #[test]
+    fn test_store_and_read() -> Result<(), String> {
+        let cache_dir = PathBuf::from("/tmp/test_feed_cache");
+        let cache = FeedCache::new(cache_dir.clone());
+        let url = "https://www.example.com/feed.xml";
+        let validators = FeedValidators {
+            etag: Some("\"abc\"".to_owned()),
+            last_modified: None,
+        };
+
+        assert!(cache.read_validators(url).is_empty());
+        cache
+            .store(url, b"<rss></rss>", &validators)
+            .expect("Storing in cache failed");
+        assert_eq!(cache.read_validators(url), validators);
+        assert_eq!(cache.read_content(url).unwrap(), b"<rss></rss>");
+
+        let new_url = "https://www.example.com/moved_feed.xml";
+        cache
+            .move_entry(url, new_url)
+            .expect("Moving of cache entry failed");
+        assert!(cache.read_content(url).is_err());
+        assert_eq!(cache.read_validators(new_url), validators);
+
+        cache.delete(new_url).expect("Deletion from cache failed");
+        assert!(cache.read_validators(new_url).is_empty());
+
+        fs::remove_dir_all(cache_dir).expect("Cleanup of test failed");
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use reqwest::{RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+
+/// Authentication data that must be sent along with every request made for a private feed
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedCredentials {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl FeedCredentials {
+    pub fn is_empty(&self) -> bool {
+        self.username.is_none() && self.headers.is_empty()
+    }
+
+    /// Adds HTTP basic authentication and extra headers to the request
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(username) = &self.username {
+            request = request.basic_auth(username, self.password.as_ref());
+        }
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    /// Adds the credentials to the request only if it is sent to the origin (scheme, host and port) of the feed,
+    /// so that they never reach another server a redirection or a link points to
+    pub fn apply_on_origin(
+        &self,
+        request: RequestBuilder,
+        feed_url: &Url,
+        request_url: &Url,
+    ) -> RequestBuilder {
+        if feed_url.origin() != request_url.origin() {
+            return request;
+        }
+        self.apply(request)
+    }
+}
+
+// Secrets must never end up in logs
+impl fmt::Debug for FeedCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeedCredentials")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("headers", &self.headers.keys().collect::<Vec<&String>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::header::AUTHORIZATION;
+    use test_case::test_case;
+
+    #[test]
+    fn test_apply() {
+        let credentials = FeedCredentials {
+            username: Some("user".to_owned()),
+            password: Some("password".to_owned()),
+            headers: BTreeMap::from([("X-Api-Key".to_owned(), "secret".to_owned())]),
+        };
+        let request = credentials
+            .apply(reqwest::Client::new().get("https://www.example.com/feed.xml"))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.headers()[AUTHORIZATION],
+            "Basic dXNlcjpwYXNzd29yZA=="
+        );
+        assert_eq!(request.headers()["X-Api-Key"], "secret");
+    }
+
+    #[test_case("https://www.example.com/archive.xml" => true; "Same origin")]
+    #[test_case("https://cdn.example.com/feed.xml" => false; "Other host")]
+    #[test_case("http://www.example.com/feed.xml" => false; "Other scheme")]
+    #[test_case("https://www.example.com:8443/feed.xml" => false; "Other port")]
+    fn test_apply_on_origin(request_url: &str) -> bool {
+        let credentials = FeedCredentials {
+            headers: BTreeMap::from([("X-Api-Key".to_owned(), "secret".to_owned())]),
+            ..Default::default()
+        };
+        let request_url = Url::parse(request_url).unwrap();
+        let request = credentials
+            .apply_on_origin(
+                reqwest::Client::new().get(request_url.clone()),
+                &Url::parse("https://www.example.com/feed.xml").unwrap(),
+                &request_url,
+            )
+            .build()
+            .unwrap();
+        request.headers().contains_key("X-Api-Key")
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let credentials = FeedCredentials {
+            username: Some("user".to_owned()),
+            password: Some("password".to_owned()),
+            headers: BTreeMap::from([("X-Api-Key".to_owned(), "secret".to_owned())]),
+        };
+        let output = format!("{:?}", credentials);
+        assert!(!output.contains("password\""));
+        assert!(!output.contains("secret"));
+    }
+}
@@ -1,25 +1,65 @@
+use std::collections::BTreeMap;
 use std::io;
 
-use futures::future::try_join_all;
+use log::{info, warn};
 use tokio::sync::RwLock;
 
 use crate::channel_tuple::ChannelTuple;
-use crate::rss_feed_reading::utils::get_feed;
+use crate::http_settings::HttpClient;
+use crate::local_storage::credential_store::CredentialStore;
+use crate::local_storage::feed_cache::FeedCache;
+use crate::local_storage::feed_credentials::FeedCredentials;
+use crate::opml::{opml_import_report::OpmlImportReport, opml_outline::OpmlOutline};
+use crate::rss_feed_reading::feed_move::FeedMove;
+use crate::rss_feed_reading::feed_source::FeedSource;
+use crate::rss_feed_reading::utils::{get_feed, get_feeds};
+use crate::url_storage::subscription::Subscription;
 use crate::url_storage::url_storer::UrlStorer;
 
 pub struct RssProvider<T: UrlStorer> {
     rss_feeds: tokio::sync::RwLock<Vec<String>>,
     url_storer: T,
+    feed_cache: FeedCache,
+    credential_store: CredentialStore,
+    feed_moves: Vec<FeedMove>,
+    http_client: HttpClient,
+    offline: bool,
 }
 
 impl<T: UrlStorer> RssProvider<T> {
-    pub fn new(mut url_storer: T) -> RssProvider<T> {
+    pub fn new(
+        mut url_storer: T,
+        feed_cache: FeedCache,
+        credential_store: CredentialStore,
+    ) -> RssProvider<T> {
         RssProvider {
             rss_feeds: RwLock::new(url_storer.get_urls().unwrap()),
             url_storer,
+            feed_cache,
+            credential_store,
+            feed_moves: vec![],
+            http_client: HttpClient::default(),
+            offline: false,
         }
     }
 
+    /// In offline mode, feeds are only read from the local cache
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn set_http_client(&mut self, http_client: HttpClient) {
+        self.http_client = http_client;
+    }
+
+    pub fn get_http_client(&self) -> &HttpClient {
+        &self.http_client
+    }
+
     pub async fn add_url(&mut self, url: &str) -> Result<(), io::Error> {
         let url_string = String::from(url);
         if !self.rss_feeds.read().await.contains(&url_string) {
@@ -33,41 +73,216 @@ impl<T: UrlStorer> RssProvider<T> {
     pub fn delete_url(&mut self, url: &str) -> Result<(), io::Error> {
         self.rss_feeds.get_mut().retain(|u| u != url);
         self.url_storer.delete_url(url)?;
+        self.feed_cache.delete(url)?;
+        if let Err(e) = self.credential_store.delete_credentials(url) {
+            warn!(
+                "Could not delete credentials of feed on URL {} : {}",
+                url, e
+            );
+        }
         Ok(())
     }
 
-    pub async fn get_all_feeds(&mut self) -> (Vec<ChannelTuple>, Vec<String>) {
-        let rss_feeds = &mut self.rss_feeds;
-        let locked_rss_feeds = rss_feeds.get_mut();
-
-        let feeds: tokio::sync::Mutex<Vec<ChannelTuple>> = tokio::sync::Mutex::new(vec![]);
-        let faulty_feeds: tokio::sync::Mutex<Vec<String>> = tokio::sync::Mutex::new(vec![]);
-
-        let mut get_feed_futures = vec![];
+    pub async fn get_urls(&self) -> Vec<String> {
+        self.rss_feeds.read().await.clone()
+    }
 
-        for f in locked_rss_feeds {
-            get_feed_futures.push(async {
-                let feed = get_feed(f).await;
-                if let Some(f) = feed {
-                    feeds.lock().await.push(f);
-                } else {
-                    faulty_feeds.lock().await.push(f.clone());
+    /// Subscribes to every feed of the given outlines, skipping the ones already subscribed
+    pub async fn import_outlines(&mut self, outlines: Vec<OpmlOutline>) -> OpmlImportReport {
+        let mut report = OpmlImportReport::default();
+        for outline in outlines {
+            let url = match outline.xml_url {
+                Some(ref u) => u.clone(),
+                None => {
+                    report.failures.push((
+                        outline.display_name(),
+                        "Missing xmlUrl attribute".to_owned(),
+                    ));
+                    continue;
                 }
-                /* Lol ??? */
-                if false {
-                    return Err(());
+            };
+            if reqwest::Url::parse(&url).is_err() {
+                report
+                    .failures
+                    .push((outline.display_name(), format!("Invalid URL {}", url)));
+                continue;
+            }
+            match self.add_url(&url).await {
+                Ok(_) => report.imported_urls.push(url),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    report.duplicate_urls.push(url)
                 }
-                Ok(())
-            });
+                Err(e) => report
+                    .failures
+                    .push((outline.display_name(), e.to_string())),
+            }
         }
-        try_join_all(get_feed_futures)
-            .await
-            .expect("Future parallelized retrieving failed");
+        report
+    }
+
+    pub async fn get_feed<'a>(&mut self, url: &'a str) -> Option<(ChannelTuple<'a>, FeedSource)> {
+        let credentials = self.get_credentials(url);
+        let (channel, source, feed_move) = get_feed(
+            url,
+            &self.feed_cache,
+            credentials.as_ref(),
+            &self.http_client,
+            self.offline,
+        )
+        .await?;
+        self.feed_moves.extend(feed_move);
+        Some((channel, source))
+    }
+
+    /// Registers moves of feeds detected while fetching them, so that they are applied by the next call to `apply_feed_moves`
+    pub fn add_feed_moves(&mut self, feed_moves: impl IntoIterator<Item = FeedMove>) {
+        self.feed_moves.extend(feed_moves);
+    }
+
+    /// Subscribes to the new URL of every feed that moved, and returns the moves that were applied
+    pub fn apply_feed_moves(&mut self) -> Vec<FeedMove> {
+        let mut applied_moves = vec![];
+        for feed_move in std::mem::take(&mut self.feed_moves) {
+            match self.apply_feed_move(&feed_move) {
+                Ok(_) => applied_moves.push(feed_move),
+                Err(e) => warn!(
+                    "Could not move feed from {} to {} : {}",
+                    feed_move.old_url, feed_move.new_url, e
+                ),
+            }
+        }
+        applied_moves
+    }
+
+    fn apply_feed_move(&mut self, feed_move: &FeedMove) -> Result<(), io::Error> {
+        let (old_url, new_url) = (&feed_move.old_url, &feed_move.new_url);
+        let feeds = self.rss_feeds.get_mut();
+        if !feeds.contains(old_url) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        if feeds.contains(new_url) {
+            // The new URL is already subscribed to, so the old subscription is a mere duplicate
+            return self.delete_url(old_url);
+        }
+
+        self.url_storer.replace_url(old_url, new_url)?;
+        feeds
+            .iter_mut()
+            .filter(|u| *u == old_url)
+            .for_each(|u| *u = new_url.clone());
+        if let Err(e) = self.feed_cache.move_entry(old_url, new_url) {
+            warn!(
+                "Could not move cached copy of feed on URL {} : {}",
+                old_url, e
+            );
+        }
+        if let Err(e) = self.credential_store.move_credentials(old_url, new_url) {
+            warn!(
+                "Could not move credentials of feed on URL {} : {}",
+                old_url, e
+            );
+        }
+        info!("Feed on URL {} moved to {}", old_url, new_url);
+        Ok(())
+    }
+
+    pub fn get_credentials(&self, url: &str) -> Option<FeedCredentials> {
+        match self.credential_store.get_credentials(url) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Could not read credentials of feed on URL {} : {}", url, e);
+                None
+            }
+        }
+    }
+
+    pub fn get_all_credentials(&self) -> BTreeMap<String, FeedCredentials> {
+        match self.credential_store.get_all_credentials() {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Could not read credentials of feeds : {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
 
-        let feeds: Vec<ChannelTuple> = feeds.into_inner();
-        let faulty_feeds: Vec<String> = faulty_feeds.into_inner();
+    pub fn get_credential_store(&mut self) -> &mut CredentialStore {
+        &mut self.credential_store
+    }
+
+    pub fn get_subscriptions(&mut self) -> Result<Vec<Subscription>, io::Error> {
+        self.url_storer.get_subscriptions()
+    }
+
+    /// Returns the URLs of the feeds that must be fetched, i.e. the ones that are not paused
+    pub fn get_active_urls(&mut self) -> Result<Vec<String>, io::Error> {
+        Ok(self
+            .get_subscriptions()?
+            .into_iter()
+            .filter(|s| !s.paused)
+            .map(|s| s.url)
+            .collect())
+    }
+
+    pub fn record_successful_fetches(&mut self, urls: &[&str]) -> Result<(), io::Error> {
+        self.url_storer.record_successful_fetches(urls)
+    }
+
+    pub fn set_alias(&mut self, url: &str, alias: Option<&str>) -> Result<(), io::Error> {
+        self.url_storer.set_alias(url, alias)
+    }
+
+    pub fn set_paused(&mut self, url: &str, paused: bool) -> Result<(), io::Error> {
+        self.url_storer.set_paused(url, paused)
+    }
+
+    /// Fetches all the feeds that are not paused
+    pub async fn get_all_feeds(&mut self) -> (Vec<(ChannelTuple<'_>, FeedSource)>, Vec<String>) {
+        let paused_urls: Vec<String> = match self.url_storer.get_subscriptions() {
+            Ok(s) => s.into_iter().filter(|s| s.paused).map(|s| s.url).collect(),
+            Err(e) => {
+                warn!("Could not read subscriptions metadata : {}", e);
+                vec![]
+            }
+        };
+        let credentials = self.get_all_credentials();
+        let urls = self
+            .rss_feeds
+            .get_mut()
+            .iter()
+            .filter(|u| !paused_urls.contains(*u))
+            .map(|u| u.as_str())
+            .collect::<Vec<&str>>();
+        let (feeds, faulty_feeds) = get_feeds(
+            &urls,
+            &self.feed_cache,
+            &credentials,
+            &self.http_client,
+            self.offline,
+        )
+        .await;
+        let feeds = feeds
+            .into_iter()
+            .map(|(channel, source, feed_move)| {
+                self.feed_moves.extend(feed_move);
+                (channel, source)
+            })
+            .collect::<Vec<(ChannelTuple, FeedSource)>>();
+
+        let fetched_urls = feeds
+            .iter()
+            .filter(|(_, source)| *source != FeedSource::Cache)
+            .map(|(channel, _)| channel.0)
+            .collect::<Vec<&str>>();
+        if let Err(e) = self.url_storer.record_successful_fetches(&fetched_urls) {
+            warn!("Could not record fetch dates of feeds : {}", e);
+        }
         (feeds, faulty_feeds)
     }
+
+    pub fn get_feed_cache(&self) -> &FeedCache {
+        &self.feed_cache
+    }
 }
 
 #[cfg(test)]
@@ -89,17 +304,104 @@ mod tests {
         }
     }
 
+    use crate::opml::opml_outline::OpmlOutline;
     use crate::url_storage::file_url_storer::UrlStorer;
 
     use super::RssProvider;
+    use crate::local_storage::credential_store::CredentialStore;
+    use crate::local_storage::feed_cache::FeedCache;
+    use crate::rss_feed_reading::feed_move::FeedMove;
+
+    fn build_dummy_feed_cache() -> FeedCache {
+        FeedCache::new(std::env::temp_dir().join("test_rss_provider_cache"))
+    }
+
+    fn build_dummy_credential_store() -> CredentialStore {
+        CredentialStore::new(std::env::temp_dir().join("test_rss_provider_credentials.json"))
+    }
+
     #[test]
 
     async fn test_add_url() -> Result<(), String> {
-        let mut rss_provider = RssProvider::new(DummyUrlStorer {});
+        let mut rss_provider = RssProvider::new(
+            DummyUrlStorer {},
+            build_dummy_feed_cache(),
+            build_dummy_credential_store(),
+        );
         if let Err(e) = rss_provider.add_url("https://www.toto.com").await {
             return Err(e.to_string());
         }
         assert_eq!(rss_provider.rss_feeds.read().await.len(), 1);
         Ok(())
     }
+
+    #[test]
+    async fn test_import_outlines() -> Result<(), String> {
+        let mut rss_provider = RssProvider::new(
+            DummyUrlStorer {},
+            build_dummy_feed_cache(),
+            build_dummy_credential_store(),
+        );
+        rss_provider
+            .add_url("https://www.toto.com/feed.xml")
+            .await
+            .unwrap();
+        let outlines = vec![
+            OpmlOutline::new(None, Some("https://www.toto.com/feed.xml".to_owned())),
+            OpmlOutline::new(None, Some("https://www.tata.com/feed.xml".to_owned())),
+            OpmlOutline::new(Some("No URL".to_owned()), None),
+            OpmlOutline::new(Some("Bad URL".to_owned()), Some("not an url".to_owned())),
+        ];
+
+        let report = rss_provider.import_outlines(outlines).await;
+
+        assert_eq!(report.imported_urls, vec!["https://www.tata.com/feed.xml"]);
+        assert_eq!(report.duplicate_urls, vec!["https://www.toto.com/feed.xml"]);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(rss_provider.get_urls().await.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_apply_feed_moves() -> Result<(), String> {
+        let mut rss_provider = RssProvider::new(
+            DummyUrlStorer {},
+            build_dummy_feed_cache(),
+            build_dummy_credential_store(),
+        );
+        for url in [
+            "https://old.example.com/feed.xml",
+            "https://www.example.com/dup.xml",
+            "https://www.example.com/feed.xml",
+        ] {
+            rss_provider.add_url(url).await.unwrap();
+        }
+        rss_provider.add_feed_moves(vec![
+            FeedMove::new(
+                "https://old.example.com/feed.xml",
+                "https://new.example.com/feed.xml",
+            ),
+            FeedMove::new(
+                "https://www.example.com/dup.xml",
+                "https://www.example.com/feed.xml",
+            ),
+            FeedMove::new(
+                "https://unknown.example.com/feed.xml",
+                "https://www.example.com/other.xml",
+            ),
+        ]);
+
+        let moves = rss_provider.apply_feed_moves();
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(
+            rss_provider.get_urls().await,
+            vec![
+                "https://new.example.com/feed.xml",
+                "https://www.example.com/feed.xml"
+            ]
+        );
+        assert!(rss_provider.apply_feed_moves().is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,4 @@
+pub mod opml_import_report;
+pub mod opml_outline;
+pub mod opml_reader;
+pub mod opml_writer;
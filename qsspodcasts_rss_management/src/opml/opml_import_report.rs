@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Summary of what happened to each outline of an imported OPML file
+#[derive(Debug, Default)]
+pub struct OpmlImportReport {
+    pub imported_urls: Vec<String>,
+    pub duplicate_urls: Vec<String>,
+    /// Pairs of (outline name, failure reason)
+    pub failures: Vec<(String, String)>,
+}
+
+impl fmt::Display for OpmlImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} feed(s) imported, {} already subscribed, {} failure(s)",
+            self.imported_urls.len(),
+            self.duplicate_urls.len(),
+            self.failures.len()
+        )?;
+        for (outline, reason) in &self.failures {
+            writeln!(f, "  - {} : {}", outline, reason)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+/// Subscription entry of an OPML document
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OpmlOutline {
+    pub title: Option<String>,
+    pub xml_url: Option<String>,
+}
+
+impl OpmlOutline {
+    pub fn new(title: Option<String>, xml_url: Option<String>) -> OpmlOutline {
+        OpmlOutline { title, xml_url }
+    }
+
+    /// Returns something that allows the user to identify the outline in a report
+    pub fn display_name(&self) -> String {
+        self.title
+            .as_ref()
+            .or(self.xml_url.as_ref())
+            .cloned()
+            .unwrap_or_else(|| "Unnamed outline".to_owned())
+    }
+}
@@ -0,0 +1,113 @@
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::opml_outline::OpmlOutline;
+
+fn build_invalid_data_error(e: impl ToString) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, e.to_string())
+}
+
+fn read_attribute(element: &BytesStart, name: &str) -> Result<Option<String>, io::Error> {
+    match element
+        .try_get_attribute(name)
+        .map_err(build_invalid_data_error)?
+    {
+        Some(a) => {
+            let raw_value = String::from_utf8_lossy(&a.value);
+            Ok(Some(
+                unescape(&raw_value)
+                    .map_err(build_invalid_data_error)?
+                    .into_owned(),
+            ))
+        }
+        None => Ok(None),
+    }
+}
+
+fn build_outline(element: &BytesStart) -> Result<OpmlOutline, io::Error> {
+    let title = match read_attribute(element, "title")? {
+        Some(t) => Some(t),
+        None => read_attribute(element, "text")?,
+    };
+    let xml_url = read_attribute(element, "xmlUrl")?;
+    Ok(OpmlOutline::new(title, xml_url))
+}
+
+/// Extracts the subscription outlines of an OPML document.
+///
+/// Outlines that have children and no `xmlUrl` are considered as categories and are not
+/// returned. Leaf outlines without `xmlUrl` are returned anyway so that the caller can report them.
+pub fn read_opml(content: &str) -> Result<Vec<OpmlOutline>, io::Error> {
+    let mut reader = Reader::from_str(content);
+    let mut outlines = vec![];
+
+    loop {
+        match reader.read_event().map_err(build_invalid_data_error)? {
+            Event::Start(e) if e.local_name().as_ref() == b"outline" => {
+                let outline = build_outline(&e)?;
+                if outline.xml_url.is_some() {
+                    outlines.push(outline);
+                }
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"outline" => {
+                outlines.push(build_outline(&e)?);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(outlines)
+}
+
+pub fn read_opml_file(path: &Path) -> Result<Vec<OpmlOutline>, io::Error> {
+    let content = std::fs::read_to_string(path)?;
+    read_opml(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_nested_outlines() -> Result<(), String> {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>Subscriptions</title></head>
+  <body>
+    <outline text="News">
+      <outline type="rss" text="First" xmlUrl="https://www.example.com/first.xml"/>
+      <outline type="rss" title="Second &amp; co" text="Ignored" xmlUrl="https://www.example.com/second.xml"/>
+    </outline>
+    <outline text="No URL"/>
+  </body>
+</opml>"#;
+        let outlines = read_opml(content).unwrap();
+        assert_eq!(
+            outlines,
+            vec![
+                OpmlOutline::new(
+                    Some("First".to_owned()),
+                    Some("https://www.example.com/first.xml".to_owned())
+                ),
+                OpmlOutline::new(
+                    Some("Second & co".to_owned()),
+                    Some("https://www.example.com/second.xml".to_owned())
+                ),
+                OpmlOutline::new(Some("No URL".to_owned()), None),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_invalid_document() -> Result<(), String> {
+        let error = read_opml("<opml><body><outline></body></opml>").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+use std::path::Path;
+
+use quick_xml::escape::escape;
+
+use super::opml_outline::OpmlOutline;
+
+pub fn write_opml(outlines: &[OpmlOutline]) -> String {
+    let mut content = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <opml version=\"2.0\">\n  \
+         <head>\n    <title>QSSPodcasts subscriptions</title>\n  </head>\n  \
+         <body>\n",
+    );
+    for outline in outlines.iter().filter(|o| o.xml_url.is_some()) {
+        let url = outline.xml_url.as_ref().unwrap();
+        let title = escape(outline.title.as_deref().unwrap_or(url));
+        content.push_str(&format!(
+            "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{}\"/>\n",
+            escape(url.as_str())
+        ));
+    }
+    content.push_str("  </body>\n</opml>\n");
+    content
+}
+
+pub fn write_opml_file(path: &Path, outlines: &[OpmlOutline]) -> Result<(), io::Error> {
+    std::fs::write(path, write_opml(outlines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opml::opml_reader::read_opml;
+
+    #[test]
+    fn test_write_then_read() -> Result<(), String> {
+        let outlines = vec![
+            OpmlOutline::new(
+                Some("Title with <special> & \"chars\"".to_owned()),
+                Some("https://www.example.com/feed.xml?a=1&b=2".to_owned()),
+            ),
+            OpmlOutline::new(None, Some("https://www.example.com/other.xml".to_owned())),
+        ];
+        let read_outlines = read_opml(&write_opml(&outlines)).unwrap();
+        assert_eq!(read_outlines.len(), 2);
+        assert_eq!(read_outlines[0], outlines[0]);
+        // Outlines without title get their URL as title
+        assert_eq!(read_outlines[1].title, outlines[1].xml_url);
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+use podcast_management::data_objects::chapter::Chapter;
+use serde::Deserialize;
+
+use crate::http_settings::HttpClient;
+
+/// Chapters file referenced by the podcast:chapters tag of a feed
+#[derive(Debug, Deserialize)]
+struct JsonChaptersFile {
+    chapters: Vec<JsonChapter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonChapter {
+    /// Position of the beginning of the chapter, in seconds
+    start_time: f64,
+    title: Option<String>,
+    /// Chapters with this set to false are not supposed to be shown to the user
+    toc: Option<bool>,
+}
+
+/// Sorts the chapters by start, naming the ones that have no title after their position
+fn sort_chapters(chapters: Vec<(Option<String>, Duration)>) -> Vec<Chapter> {
+    let mut chapters = chapters;
+    chapters.sort_by_key(|(_, start)| *start);
+    chapters
+        .into_iter()
+        .enumerate()
+        .map(|(i, (title, start))| {
+            let title = title
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or(format!("Chapter {}", i + 1));
+            Chapter::new(title.trim(), start)
+        })
+        .collect()
+}
+
+/// Returns the element IDs of the chapters the top-level table of contents leads to, if the tag has one
+fn find_listed_chapter_ids(tag: &id3::Tag) -> Option<HashSet<&str>> {
+    let tables = tag
+        .tables_of_contents()
+        .map(|t| (t.element_id.as_str(), t))
+        .collect::<HashMap<&str, &id3::frame::TableOfContents>>();
+    let top_level_table = tag.tables_of_contents().find(|t| t.top_level)?;
+
+    let mut chapter_ids = HashSet::new();
+    let mut visited_tables = HashSet::from([top_level_table.element_id.as_str()]);
+    let mut tables_to_visit = vec![top_level_table];
+    while let Some(table) = tables_to_visit.pop() {
+        for element in &table.elements {
+            match tables.get(element.as_str()) {
+                Some(t) if visited_tables.insert(element.as_str()) => tables_to_visit.push(t),
+                Some(_) => {}
+                None => {
+                    chapter_ids.insert(element.as_str());
+                }
+            }
+        }
+    }
+    Some(chapter_ids)
+}
+
+/// Reads the chapters of an ID3 tag, given by its CHAP frames.
+///
+/// If the tag has a table of contents (CTOC frames), only the chapters it lists are kept.
+fn chapters_from_tag(tag: &id3::Tag) -> Vec<Chapter> {
+    let listed_chapter_ids = find_listed_chapter_ids(tag);
+    let chapters = tag
+        .chapters()
+        .filter(|c| match &listed_chapter_ids {
+            Some(ids) => ids.contains(c.element_id.as_str()),
+            None => true,
+        })
+        .map(|c| {
+            let title = c
+                .frames
+                .iter()
+                .find(|f| f.id() == "TIT2")
+                .and_then(|f| f.content().text())
+                .map(|t| t.to_owned());
+            (title, Duration::from_millis(c.start_time.into()))
+        })
+        .collect();
+    sort_chapters(chapters)
+}
+
+/// Reads the chapters of an audio file from its ID3 tag, there being none if the file has no tag
+pub fn read_id3_chapters(path: &Path) -> Vec<Chapter> {
+    match id3::Tag::read_from_path(path) {
+        Ok(tag) => chapters_from_tag(&tag),
+        Err(_) => vec![],
+    }
+}
+
+/// Parses a JSON chapters file, as described by the podcast namespace
+pub fn parse_json_chapters(content: &[u8]) -> Result<Vec<Chapter>, serde_json::Error> {
+    let file: JsonChaptersFile = serde_json::from_slice(content)?;
+    let chapters = file
+        .chapters
+        .into_iter()
+        .filter(|c| c.toc != Some(false))
+        .filter(|c| c.start_time.is_finite() && c.start_time >= 0.0)
+        .map(|c| (c.title, Duration::from_secs_f64(c.start_time)))
+        .collect();
+    Ok(sort_chapters(chapters))
+}
+
+/// Downloads and parses the JSON chapters file referenced by the feed of an episode
+pub async fn fetch_json_chapters(
+    url: &str,
+    http_client: &HttpClient,
+) -> Result<Vec<Chapter>, Box<dyn Error + Send + Sync>> {
+    let request = http_client
+        .client()
+        .get(url)
+        .timeout(http_client.settings().feed_timeout);
+    let response = http_client
+        .settings()
+        .send_with_retry(request)
+        .await?
+        .error_for_status()?;
+    let content = response.bytes().await?;
+    Ok(parse_json_chapters(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use id3::frame::{Chapter as ChapterFrame, TableOfContents};
+    use id3::{Frame, TagLike};
+
+    fn build_chapter_frame(element_id: &str, start_time: u32, title: Option<&str>) -> ChapterFrame {
+        ChapterFrame {
+            element_id: element_id.to_owned(),
+            start_time,
+            end_time: start_time + 1000,
+            start_offset: 0xffffffff,
+            end_offset: 0xffffffff,
+            frames: title.map(|t| Frame::text("TIT2", t)).into_iter().collect(),
+        }
+    }
+
+    fn build_table_of_contents(
+        element_id: &str,
+        top_level: bool,
+        elements: &[&str],
+    ) -> TableOfContents {
+        TableOfContents {
+            element_id: element_id.to_owned(),
+            top_level,
+            ordered: true,
+            elements: elements.iter().map(|e| e.to_string()).collect(),
+            frames: vec![],
+        }
+    }
+
+    #[test]
+    fn test_chapters_from_tag() {
+        let mut tag = id3::Tag::new();
+        tag.add_frame(build_chapter_frame("chp2", 60000, Some("Interview")));
+        tag.add_frame(build_chapter_frame("chp1", 0, Some("Introduction")));
+        tag.add_frame(build_chapter_frame("chp3", 600500, None));
+        assert_eq!(
+            chapters_from_tag(&tag),
+            vec![
+                Chapter::new("Introduction", Duration::ZERO),
+                Chapter::new("Interview", Duration::from_secs(60)),
+                Chapter::new("Chapter 3", Duration::from_millis(600500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chapters_from_tag_with_table_of_contents() {
+        let mut tag = id3::Tag::new();
+        tag.add_frame(build_chapter_frame("chp1", 0, Some("Introduction")));
+        tag.add_frame(build_chapter_frame("chp2", 60000, Some("Interview")));
+        tag.add_frame(build_chapter_frame("ad", 30000, Some("Advertisement")));
+        tag.add_frame(build_table_of_contents("toc", true, &["chp1", "part"]));
+        tag.add_frame(build_table_of_contents("part", false, &["chp2", "toc"]));
+        let titles = chapters_from_tag(&tag)
+            .into_iter()
+            .map(|c| c.title)
+            .collect::<Vec<String>>();
+        assert_eq!(titles, vec!["Introduction", "Interview"]);
+    }
+
+    #[test]
+    fn test_parse_json_chapters() {
+        let content = r#"{
+            "version": "1.2.0",
+            "chapters": [
+                {"startTime": 95.5, "title": "Interview", "url": "https://www.example.com"},
+                {"startTime": 0, "title": "Introduction"},
+                {"startTime": 300, "title": "Hidden", "toc": false},
+                {"startTime": 600}
+            ]
+        }"#;
+        assert_eq!(
+            parse_json_chapters(content.as_bytes()).unwrap(),
+            vec![
+                Chapter::new("Introduction", Duration::ZERO),
+                Chapter::new("Interview", Duration::from_millis(95500)),
+                Chapter::new("Chapter 3", Duration::from_secs(600)),
+            ]
+        );
+        assert!(parse_json_chapters(b"[]").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use std::error::Error;
+
+use rss::Channel;
+
+/// Parsed content of a feed, which can either be a RSS channel or an Atom feed
+#[derive(Debug, Clone)]
+pub enum Feed {
+    Rss(Box<Channel>),
+    Atom(Box<atom_syndication::Feed>),
+}
+
+impl Feed {
+    /// Parses the content as a RSS channel, or as an Atom feed if the root element is not a RSS one
+    pub fn read_from(content: &[u8]) -> Result<Feed, Box<dyn Error>> {
+        match Channel::read_from(content) {
+            Ok(channel) => Ok(Feed::Rss(Box::new(channel))),
+            Err(rss::Error::InvalidStartTag) => Ok(Feed::Atom(Box::new(
+                atom_syndication::Feed::read_from(content)?,
+            ))),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            Feed::Rss(channel) => channel.title(),
+            Feed::Atom(feed) => feed.title().as_str(),
+        }
+    }
+
+    /// URL the publisher announced the feed moved to, if any
+    pub fn new_feed_url(&self) -> Option<&str> {
+        match self {
+            Feed::Rss(channel) => channel.itunes_ext().and_then(|e| e.new_feed_url()),
+            Feed::Atom(_) => None,
+        }
+    }
+
+    pub fn item_count(&self) -> usize {
+        match self {
+            Feed::Rss(channel) => channel.items().len(),
+            Feed::Atom(feed) => feed.entries().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(r#"<rss version="2.0"><channel><title>Some RSS</title><link>https://www.example.com</link><description>RSS</description></channel></rss>"# => "Some RSS"; "RSS document")]
+    #[test_case(r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Some Atom</title><id>urn:uuid:1</id><updated>2023-01-01T00:00:00Z</updated></feed>"# => "Some Atom"; "Atom document")]
+    fn test_read_from(content: &str) -> String {
+        Feed::read_from(content.as_bytes())
+            .unwrap()
+            .title()
+            .to_owned()
+    }
+
+    #[test]
+    fn test_read_from_invalid_document() {
+        assert!(Feed::read_from(b"<html><body></body></html>").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fs;
+
+use log::warn;
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use reqwest::Url;
+
+use crate::http_settings::HttpClient;
+use crate::local_storage::feed_credentials::FeedCredentials;
+use crate::rss_feed_reading::feed::Feed;
+use crate::rss_feed_reading::utils::{
+    build_feed_request, local_file_path, send_following_redirects,
+};
+
+/// Maximum number of pages downloaded when loading the archive of a feed, the feed itself included
+pub const MAX_ARCHIVE_PAGES: usize = 200;
+
+/// Relations of the links pointing to older pages of a feed (RFC 5005)
+const ARCHIVE_LINK_RELATIONS: [&str; 2] = ["next", "prev-archive"];
+
+fn read_attribute(element: &BytesStart, name: &str) -> Option<String> {
+    let attribute = element.try_get_attribute(name).ok()??;
+    let raw_value = String::from_utf8_lossy(&attribute.value);
+    Some(
+        unescape(&raw_value)
+            .map(|v| v.into_owned())
+            .unwrap_or(raw_value.into_owned()),
+    )
+}
+
+/// Lists the URLs of the older pages of a feed.
+///
+/// Links are looked for whatever their namespace, so that `atom:link` elements of RSS feeds,
+/// links of Atom feeds and their `podcast:` equivalents are all handled.
+/// Relative URLs are resolved against the URL of the page.
+pub fn find_archive_links(content: &[u8], page_url: &Url) -> Vec<String> {
+    let mut reader = Reader::from_reader(content);
+    let mut buffer = vec![];
+    let mut links: Vec<String> = vec![];
+    loop {
+        match reader.read_event_into(&mut buffer) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"link" => {
+                let is_archive_link = read_attribute(&e, "rel")
+                    .map(|r| ARCHIVE_LINK_RELATIONS.contains(&r.trim()))
+                    .unwrap_or(false);
+                let link = read_attribute(&e, "href")
+                    .and_then(|h| page_url.join(h.trim()).ok())
+                    .map(|l| l.to_string());
+                match link {
+                    Some(link) if is_archive_link && !links.contains(&link) => links.push(link),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                warn!("Could not read links of page {} : {}", page_url, e);
+                break;
+            }
+            _ => {}
+        }
+        buffer.clear();
+    }
+    links
+}
+
+/// Downloads a page of the feed, its credentials being only sent if the page is on the origin of the feed
+async fn fetch_page(
+    url: &str,
+    feed_url: &Url,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    if let Some(path) = local_file_path(url) {
+        return Ok(fs::read(path)?);
+    }
+    let build_request =
+        |request_url: &Url| build_feed_request(http_client, request_url, feed_url, credentials);
+    let response = send_following_redirects(url, build_request, http_client, true, &mut vec![])
+        .await?
+        .error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Downloads the feed and all the older pages it links to, following links from page to page.
+///
+/// Pages that cannot be downloaded or parsed are skipped, only the failure of the first one is an error.
+pub async fn get_archive_pages(
+    url: &str,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+) -> Result<Vec<Feed>, Box<dyn Error>> {
+    let feed_url = Url::parse(url)?;
+    let mut pages = vec![];
+    let mut visited_urls = HashSet::from([url.to_owned()]);
+    let mut urls_to_visit = VecDeque::from([url.to_owned()]);
+    while let Some(page_url) = urls_to_visit.pop_front() {
+        if pages.len() == MAX_ARCHIVE_PAGES {
+            warn!("Archive of feed {} has too many pages, stopping", url);
+            break;
+        }
+        let content = match fetch_page(&page_url, &feed_url, credentials, http_client).await {
+            Ok(c) => c,
+            Err(e) if pages.is_empty() => return Err(e),
+            Err(e) => {
+                warn!("Could not download archive page {} : {}", page_url, e);
+                continue;
+            }
+        };
+        let feed = match Feed::read_from(&content[..]) {
+            Ok(f) => f,
+            Err(e) if pages.is_empty() => return Err(e),
+            Err(e) => {
+                warn!("Could not read archive page {} : {}", page_url, e);
+                continue;
+            }
+        };
+        for link in find_archive_links(&content, &Url::parse(&page_url)?) {
+            if visited_urls.insert(link.clone()) {
+                urls_to_visit.push_back(link);
+            }
+        }
+        pages.push(feed);
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+    use test_case::test_case;
+
+    // Allows to test async functions
+    use tokio_test;
+    macro_rules! aw {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    const PAGE_URL: &str = "https://www.example.com/feed.xml";
+
+    #[test_case(r#"<rss xmlns:atom="http://www.w3.org/2005/Atom"><channel><atom:link rel="self" href="/feed.xml"/><atom:link rel="next" href="/feed.xml?page=2"/></channel></rss>"# => vec!["https://www.example.com/feed.xml?page=2".to_owned()]; "RSS next link")]
+    #[test_case(r#"<feed xmlns="http://www.w3.org/2005/Atom"><link rel="prev-archive" href="https://archive.example.com/2019.xml"/><link rel="alternate" href="/"/></feed>"# => vec!["https://archive.example.com/2019.xml".to_owned()]; "Atom archive link")]
+    #[test_case(r#"<rss><channel><link>https://www.example.com</link></channel></rss>"# => Vec::<String>::new(); "No archive link")]
+    fn test_find_archive_links(content: &str) -> Vec<String> {
+        find_archive_links(content.as_bytes(), &Url::parse(PAGE_URL).unwrap())
+    }
+
+    #[test]
+    fn test_get_archive_pages() {
+        let directory = std::env::temp_dir().join("test_get_archive_pages");
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        let write_page = |name: &str, title: &str, next: Option<&str>| {
+            let link = next
+                .map(|n| format!(r#"<atom:link rel="next" href="{}"/>"#, n))
+                .unwrap_or_default();
+            let content = format!(
+                r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel><title>{}</title><link>https://www.example.com</link><description>Page</description>{}</channel></rss>"#,
+                title, link
+            );
+            fs::write(directory.join(name), content).unwrap();
+        };
+        // The last page links back to the first one, which must not be loaded twice
+        write_page("feed.xml", "Page 1", Some("page_2.xml"));
+        write_page("page_2.xml", "Page 2", Some("page_3.xml"));
+        write_page("page_3.xml", "Page 3", Some("feed.xml"));
+
+        let url = Url::from_file_path(Path::new(&directory).join("feed.xml")).unwrap();
+        let pages = aw!(get_archive_pages(
+            url.as_str(),
+            None,
+            &HttpClient::default()
+        ))
+        .unwrap();
+        assert_eq!(
+            pages.iter().map(|p| p.title()).collect::<Vec<&str>>(),
+            vec!["Page 1", "Page 2", "Page 3"]
+        );
+
+        fs::remove_dir_all(directory).expect("Cleanup of test failed");
+    }
+}
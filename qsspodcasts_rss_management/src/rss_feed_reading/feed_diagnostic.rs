@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+/// Report on the retrieval of a feed, meant to help understanding why a feed does not show up as expected
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FeedDiagnostic {
+    pub url: String,
+    /// HTTP status of the last response received
+    pub status: Option<u16>,
+    /// URLs the feed was redirected to, in order
+    pub redirects: Vec<String>,
+    pub content_type: Option<String>,
+    /// Error that prevented the feed from being downloaded or parsed
+    pub error: Option<String>,
+    pub item_count: Option<usize>,
+    /// Items that could not be turned into episodes, along with the reason why
+    pub rejected_items: Vec<(String, String)>,
+}
+
+impl FeedDiagnostic {
+    pub fn new(url: &str) -> FeedDiagnostic {
+        FeedDiagnostic {
+            url: url.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none() && self.rejected_items.is_empty()
+    }
+
+    pub fn count_rejections_by_reason(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for (_, reason) in &self.rejected_items {
+            *counts.entry(reason.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_rejections_by_reason() {
+        let mut diagnostic = FeedDiagnostic::new("https://www.example.com/feed.xml");
+        assert!(diagnostic.is_healthy());
+        diagnostic.rejected_items = vec![
+            ("First".to_owned(), "Missing enclosure".to_owned()),
+            ("Second".to_owned(), "Missing guid".to_owned()),
+            ("Third".to_owned(), "Missing enclosure".to_owned()),
+        ];
+        assert!(!diagnostic.is_healthy());
+
+        let counts = diagnostic.count_rejections_by_reason();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["Missing enclosure"], 2);
+        assert_eq!(counts["Missing guid"], 1);
+    }
+}
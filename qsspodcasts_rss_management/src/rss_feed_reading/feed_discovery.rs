@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+use reqwest::Url;
+
+use crate::http_settings::HttpClient;
+use crate::local_storage::feed_credentials::FeedCredentials;
+use crate::rss_feed_reading::feed::Feed;
+use crate::rss_feed_reading::utils::{
+    build_feed_request, local_file_path, send_following_redirects,
+};
+
+const FEED_MIME_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// Feed advertised by a web page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedCandidate {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+impl FeedCandidate {
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.url)
+    }
+}
+
+/// Parses the attributes of an HTML tag, whose name must have been stripped already.
+///
+/// Attribute names are lowercased, and values can either be quoted or not.
+fn parse_attributes(tag: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut rest = tag.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        if name_end == 0 {
+            // Stray character, e.g. the slash of a self-closing tag
+            rest = rest[1..].trim_start();
+            continue;
+        }
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = "";
+        if let Some(remaining) = rest.strip_prefix('=') {
+            let remaining = remaining.trim_start();
+            (value, rest) = match remaining.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let end = remaining[1..]
+                        .find(quote)
+                        .map(|i| i + 1)
+                        .unwrap_or(remaining.len());
+                    (&remaining[1..end], remaining.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = remaining
+                        .find(char::is_whitespace)
+                        .unwrap_or(remaining.len());
+                    (&remaining[..end], &remaining[end..])
+                }
+            };
+        }
+        let value = quick_xml::escape::unescape(value)
+            .map(|v| v.into_owned())
+            .unwrap_or(value.to_owned());
+        attributes.insert(name, value);
+        rest = rest.trim_start();
+    }
+    attributes
+}
+
+/// Lists the RSS and Atom feeds advertised by the `<link rel="alternate">` tags of an HTML page.
+///
+/// Relative URLs are resolved against the URL of the page.
+pub fn find_feed_links(html: &str, page_url: &Url) -> Vec<FeedCandidate> {
+    let lowercase_html = html.to_ascii_lowercase();
+    let mut candidates: Vec<FeedCandidate> = vec![];
+    let mut position = 0;
+    while let Some(index) = lowercase_html[position..].find("<link") {
+        let tag_start = position + index + "<link".len();
+        let tag_end = match lowercase_html[tag_start..].find('>') {
+            Some(i) => tag_start + i,
+            None => break,
+        };
+        position = tag_end;
+        if !html[tag_start..].starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>') {
+            continue;
+        }
+
+        let attributes = parse_attributes(&html[tag_start..tag_end]);
+        let is_alternate = attributes
+            .get("rel")
+            .map(|r| {
+                r.split_whitespace()
+                    .any(|t| t.eq_ignore_ascii_case("alternate"))
+            })
+            .unwrap_or(false);
+        let is_feed = attributes
+            .get("type")
+            .map(|t| FEED_MIME_TYPES.contains(&t.trim().to_ascii_lowercase().as_str()))
+            .unwrap_or(false);
+        if !is_alternate || !is_feed {
+            continue;
+        }
+        let url = match attributes.get("href").map(|h| page_url.join(h.trim())) {
+            Some(Ok(u)) => u.to_string(),
+            _ => continue,
+        };
+        if candidates.iter().any(|c| c.url == url) {
+            continue;
+        }
+        candidates.push(FeedCandidate {
+            url,
+            title: attributes
+                .get("title")
+                .map(|t| t.trim().to_owned())
+                .filter(|t| !t.is_empty()),
+        });
+    }
+    candidates
+}
+
+/// Finds the feeds available on a URL.
+///
+/// If the URL points to a feed or to a local directory, it is the only candidate returned. Otherwise the content is
+/// considered as a web page, and the feeds it advertises are returned.
+pub async fn discover_feeds(
+    url: &str,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+) -> Result<Vec<FeedCandidate>, Box<dyn Error>> {
+    let (page_url, content) = match local_file_path(url) {
+        Some(path) if path.is_dir() => {
+            return Ok(vec![FeedCandidate {
+                url: url.to_owned(),
+                title: None,
+            }])
+        }
+        Some(path) => (Url::parse(url)?, fs::read(path)?),
+        None => {
+            let feed_url = Url::parse(url)?;
+            let build_request = |request_url: &Url| {
+                build_feed_request(http_client, request_url, &feed_url, credentials)
+            };
+            let response =
+                send_following_redirects(url, build_request, http_client, true, &mut vec![])
+                    .await?
+                    .error_for_status()?;
+            let page_url = response.url().clone();
+            (page_url, response.bytes().await?.to_vec())
+        }
+    };
+
+    if let Ok(feed) = Feed::read_from(&content[..]) {
+        return Ok(vec![FeedCandidate {
+            url: url.to_owned(),
+            title: Some(feed.title().to_owned()),
+        }]);
+    }
+    Ok(find_feed_links(
+        &String::from_utf8_lossy(&content),
+        &page_url,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+    use test_case::test_case;
+
+    // Allows to test async functions
+    use tokio_test;
+    macro_rules! aw {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    const PAGE_URL: &str = "https://www.example.com/show/";
+
+    fn find_urls(html: &str) -> Vec<String> {
+        find_feed_links(html, &Url::parse(PAGE_URL).unwrap())
+            .into_iter()
+            .map(|c| c.url)
+            .collect()
+    }
+
+    #[test_case(r#"<link rel="alternate" type="application/rss+xml" href="https://feeds.example.com/show.xml">"# => vec!["https://feeds.example.com/show.xml".to_owned()]; "RSS link")]
+    #[test_case(r#"<LINK REL="Alternate" TYPE="application/atom+xml" HREF="/atom.xml" />"# => vec!["https://www.example.com/atom.xml".to_owned()]; "Uppercase Atom link with absolute path")]
+    #[test_case(r#"<link rel='alternate feed' type='application/rss+xml' href='feed.xml'>"# => vec!["https://www.example.com/show/feed.xml".to_owned()]; "Single quotes, several rel tokens and relative path")]
+    #[test_case(r#"<link rel=alternate type=application/rss+xml href=/feed?a=1&amp;b=2>"# => vec!["https://www.example.com/feed?a=1&b=2".to_owned()]; "Unquoted attributes and escaped ampersand")]
+    #[test_case(r#"<link rel="stylesheet" type="text/css" href="style.css"><link rel="alternate" type="text/html" hreflang="fr" href="/fr/">"# => Vec::<String>::new(); "Links that are not feeds")]
+    #[test_case(r#"<linkage rel="alternate" type="application/rss+xml" href="/feed.xml">"# => Vec::<String>::new(); "Other tag starting like link")]
+    #[test_case(r#"<link rel="alternate" type="application/rss+xml" href="/a.xml"><link rel="alternate" type="application/rss+xml" href="/a.xml"><link rel="alternate" type="application/atom+xml" href="/b.xml">"# => vec!["https://www.example.com/a.xml".to_owned(), "https://www.example.com/b.xml".to_owned()]; "Duplicates are removed")]
+    fn test_find_feed_links(html: &str) -> Vec<String> {
+        find_urls(html)
+    }
+
+    #[test]
+    fn test_find_feed_links_title() {
+        let html = r#"<html><head><title>Show</title>
+            <link rel="alternate" type="application/rss+xml" title="Show &amp; friends (MP3)" href="/mp3.xml">
+            <link rel="alternate" type="application/rss+xml" href="/aac.xml">
+            </head><body></body></html>"#;
+        let candidates = find_feed_links(html, &Url::parse(PAGE_URL).unwrap());
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].display_name(), "Show & friends (MP3)");
+        assert_eq!(
+            candidates[1].display_name(),
+            "https://www.example.com/aac.xml"
+        );
+    }
+
+    #[test]
+    fn test_discover_local_feeds() {
+        let test_data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data");
+        let page_url = Url::from_file_path(test_data_dir.join("page_with_feeds.html")).unwrap();
+        let candidates = aw!(discover_feeds(
+            page_url.as_str(),
+            None,
+            &HttpClient::default()
+        ))
+        .unwrap();
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|c| c.display_name())
+                .collect::<Vec<&str>>(),
+            vec!["Local show (RSS)", "Local show (Atom)"]
+        );
+
+        // A feed is its own single candidate
+        let feed_url = &candidates[0].url;
+        let candidates = aw!(discover_feeds(feed_url, None, &HttpClient::default())).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(&candidates[0].url, feed_url);
+        assert_eq!(candidates[0].display_name(), "Local show");
+    }
+}
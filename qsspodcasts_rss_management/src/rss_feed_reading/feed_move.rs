@@ -0,0 +1,60 @@
+use crate::rss_feed_reading::feed::Feed;
+
+/// Relocation of a feed announced by its host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedMove {
+    pub old_url: String,
+    pub new_url: String,
+}
+
+impl FeedMove {
+    pub fn new(old_url: &str, new_url: &str) -> FeedMove {
+        FeedMove {
+            old_url: old_url.to_owned(),
+            new_url: new_url.to_owned(),
+        }
+    }
+
+    /// Checks whether the feed fetched on `url` moved, either through the `itunes:new-feed-url` tag
+    /// or through a chain of permanent redirections ending on `permanent_redirect`.
+    ///
+    /// The tag prevails, since it is the one the publisher explicitly set.
+    pub fn detect(url: &str, feed: &Feed, permanent_redirect: Option<&str>) -> Option<FeedMove> {
+        feed.new_feed_url()
+            .map(|u| u.trim())
+            .filter(|u| reqwest::Url::parse(u).is_ok())
+            .or(permanent_redirect)
+            .filter(|new_url| *new_url != url)
+            .map(|new_url| FeedMove::new(url, new_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    const URL: &str = "https://old.example.com/feed.xml";
+
+    fn build_feed(new_feed_url: Option<&str>) -> Feed {
+        let tag = new_feed_url
+            .map(|u| format!("<itunes:new-feed-url>{}</itunes:new-feed-url>", u))
+            .unwrap_or_default();
+        let content = format!(
+            r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd"><channel><title>Moving</title><link>https://www.example.com</link><description>Moving</description>{}</channel></rss>"#,
+            tag
+        );
+        Feed::read_from(content.as_bytes()).unwrap()
+    }
+
+    #[test_case(None, None => None; "Feed did not move")]
+    #[test_case(None, Some("https://new.example.com/feed.xml") => Some("https://new.example.com/feed.xml".to_owned()); "Permanent redirection")]
+    #[test_case(Some("https://new.example.com/feed.xml"), None => Some("https://new.example.com/feed.xml".to_owned()); "New feed URL tag")]
+    #[test_case(Some("https://tag.example.com/feed.xml"), Some("https://new.example.com/feed.xml") => Some("https://tag.example.com/feed.xml".to_owned()); "Tag prevails over redirection")]
+    #[test_case(Some(URL), None => None; "Tag pointing to the current URL")]
+    #[test_case(Some("not an url"), None => None; "Invalid tag")]
+    fn test_detect(new_feed_url: Option<&str>, permanent_redirect: Option<&str>) -> Option<String> {
+        FeedMove::detect(URL, &build_feed(new_feed_url), permanent_redirect).map(|m| m.new_url)
+    }
+}
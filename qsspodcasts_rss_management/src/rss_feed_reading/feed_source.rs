@@ -0,0 +1,9 @@
+/// Tells where the content of a feed was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedSource {
+    Network,
+    /// File on the local filesystem, referenced by a `file://` URL
+    Local,
+    /// Local copy of the feed, used when it could not be fetched
+    Cache,
+}
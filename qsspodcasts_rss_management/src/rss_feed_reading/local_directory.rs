@@ -0,0 +1,187 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, TimeZone, Utc};
+use id3::TagLike;
+use log::warn;
+use reqwest::Url;
+
+use crate::rss_feed_reading::feed::Feed;
+
+const AUDIO_MIME_TYPES: [(&str, &str); 7] = [
+    ("mp3", "audio/mpeg"),
+    ("m4a", "audio/mp4"),
+    ("aac", "audio/aac"),
+    ("ogg", "audio/ogg"),
+    ("opus", "audio/opus"),
+    ("flac", "audio/flac"),
+    ("wav", "audio/wav"),
+];
+
+fn get_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    AUDIO_MIME_TYPES
+        .iter()
+        .find(|(e, _)| *e == extension)
+        .map(|(_, m)| *m)
+}
+
+fn timestamp_to_date(timestamp: id3::Timestamp) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(
+        timestamp.year,
+        timestamp.month.unwrap_or(1).into(),
+        timestamp.day.unwrap_or(1).into(),
+        timestamp.hour.unwrap_or(0).into(),
+        timestamp.minute.unwrap_or(0).into(),
+        timestamp.second.unwrap_or(0).into(),
+    )
+    .single()
+}
+
+/// Builds an item out of an audio file, using its ID3 tag when it has one.
+///
+/// The publication date falls back on the modification date of the file.
+fn build_item(path: &Path, mime_type: &str) -> Result<rss::Item, io::Error> {
+    let url = Url::from_file_path(path)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Path is not absolute"))?
+        .to_string();
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let metadata = fs::metadata(path)?;
+    let tag = id3::Tag::read_from_path(path).ok();
+
+    let title = tag
+        .as_ref()
+        .and_then(|t| t.title())
+        .map(|t| t.to_owned())
+        .or(path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or(file_name.clone());
+    // The file name is part of the description, so that episodes sharing a title
+    // (e.g. chapters of different audiobooks) can be told apart
+    let description = match tag.as_ref().and_then(|t| t.comments().next()) {
+        Some(comment) => format!("{}\n\n{}", comment.text, file_name),
+        None => file_name,
+    };
+    let pub_date = tag
+        .as_ref()
+        .and_then(|t| t.date_released().or(t.date_recorded()))
+        .and_then(timestamp_to_date)
+        .unwrap_or(DateTime::<Utc>::from(metadata.modified()?));
+
+    Ok(rss::Item {
+        title: Some(title),
+        link: Some(url.clone()),
+        description: Some(description),
+        author: tag.as_ref().and_then(|t| t.artist()).map(|a| a.to_owned()),
+        enclosure: Some(rss::Enclosure {
+            url: url.clone(),
+            length: metadata.len().to_string(),
+            mime_type: mime_type.to_owned(),
+        }),
+        guid: Some(rss::Guid {
+            value: url,
+            permalink: false,
+        }),
+        pub_date: Some(pub_date.to_rfc2822()),
+        ..Default::default()
+    })
+}
+
+/// Builds a feed out of the audio files stored in a directory, so that it can be handled like any other podcast.
+///
+/// Subdirectories are not explored, and files that cannot be read are skipped.
+pub fn read_local_directory(path: &Path) -> Result<Feed, io::Error> {
+    let mut audio_files = fs::read_dir(path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter_map(|p| get_mime_type(&p).map(|m| (p, m)))
+        .collect::<Vec<_>>();
+    audio_files.sort();
+
+    let items = audio_files
+        .iter()
+        .filter_map(|(p, m)| match build_item(p, m) {
+            Ok(i) => Some(i),
+            Err(e) => {
+                warn!("Could not read audio file {} : {}", p.display(), e);
+                None
+            }
+        })
+        .collect::<Vec<rss::Item>>();
+
+    let title = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or(path.to_string_lossy().into_owned());
+    let link = Url::from_directory_path(path)
+        .map(|u| u.to_string())
+        .unwrap_or_default();
+    Ok(Feed::Rss(Box::new(rss::Channel {
+        title,
+        link,
+        description: format!("Audio files of the local folder {}", path.display()),
+        items,
+        ..Default::default()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rss_feed_reading::utils::local_file_path;
+    use id3::{Tag, Version};
+
+    #[test]
+    fn test_read_local_directory() {
+        let directory = std::env::temp_dir().join("test_read_local_directory");
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("01_untagged.mp3"), b"").unwrap();
+        fs::write(directory.join("cover.jpg"), b"").unwrap();
+
+        let tagged_path = directory.join("02_tagged.mp3");
+        fs::write(&tagged_path, b"").unwrap();
+        let mut tag = Tag::new();
+        tag.set_title("Chapter 2");
+        tag.set_artist("Author");
+        tag.set_date_released(id3::Timestamp {
+            year: 2020,
+            month: Some(5),
+            day: Some(17),
+            hour: None,
+            minute: None,
+            second: None,
+        });
+        tag.write_to_path(&tagged_path, Version::Id3v24).unwrap();
+
+        let channel = match read_local_directory(&directory).unwrap() {
+            Feed::Rss(c) => c,
+            Feed::Atom(_) => panic!("Local directories must be read as RSS feeds"),
+        };
+        assert_eq!(channel.title, "test_read_local_directory");
+        assert_eq!(channel.items.len(), 2);
+
+        let untagged = &channel.items[0];
+        assert_eq!(untagged.title.as_deref(), Some("01_untagged"));
+        assert_eq!(untagged.description.as_deref(), Some("01_untagged.mp3"));
+        assert!(DateTime::parse_from_rfc2822(untagged.pub_date.as_ref().unwrap()).is_ok());
+
+        let tagged = &channel.items[1];
+        assert_eq!(tagged.title.as_deref(), Some("Chapter 2"));
+        assert_eq!(tagged.author.as_deref(), Some("Author"));
+        assert_eq!(
+            tagged.pub_date.as_deref(),
+            Some("Sun, 17 May 2020 00:00:00 +0000")
+        );
+        let enclosure = tagged.enclosure.as_ref().unwrap();
+        assert_eq!(enclosure.mime_type, "audio/mpeg");
+        assert_eq!(local_file_path(&enclosure.url), Some(tagged_path.clone()));
+
+        fs::remove_dir_all(directory).expect("Cleanup of test failed");
+    }
+}
@@ -1 +1,9 @@
+pub mod chapters;
+pub mod feed;
+pub mod feed_archive;
+pub mod feed_diagnostic;
+pub mod feed_discovery;
+pub mod feed_move;
+pub mod feed_source;
+pub mod local_directory;
 pub mod utils;
@@ -1,23 +1,329 @@
 use crate::channel_tuple::ChannelTuple;
-use log::info;
-use rss::Channel;
+use crate::http_settings::HttpClient;
+use crate::local_storage::feed_cache::{FeedCache, FeedValidators};
+use crate::local_storage::feed_credentials::FeedCredentials;
+use crate::rss_feed_reading::feed::Feed;
+use crate::rss_feed_reading::feed_diagnostic::FeedDiagnostic;
+use crate::rss_feed_reading::feed_move::FeedMove;
+use crate::rss_feed_reading::feed_source::FeedSource;
+use crate::rss_feed_reading::local_directory::read_local_directory;
+use futures::future::try_join_all;
+use log::{info, warn};
+use reqwest::header::{CONTENT_TYPE, IF_MODIFIED_SINCE, IF_NONE_MATCH, LOCATION};
+use reqwest::{RequestBuilder, Response, StatusCode, Url};
+use std::collections::BTreeMap;
 use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-pub async fn get_feed(url: &str) -> Option<ChannelTuple> {
-    match get_feed_inner(url).await {
-        Ok(t) => Some(t),
+const MAX_REDIRECTS: usize = 10;
+
+/// Redirection followed while downloading a URL
+pub struct Redirect {
+    /// URL the redirection points to
+    pub url: Url,
+    pub status: StatusCode,
+}
+
+impl Redirect {
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self.status,
+            StatusCode::MOVED_PERMANENTLY | StatusCode::PERMANENT_REDIRECT
+        )
+    }
+}
+
+/// Builds a request downloading the feed, or a page related to it, the credentials of the feed being only sent to its origin
+pub fn build_feed_request(
+    http_client: &HttpClient,
+    request_url: &Url,
+    feed_url: &Url,
+    credentials: Option<&FeedCredentials>,
+) -> RequestBuilder {
+    let request = http_client
+        .manual_redirect_client()
+        .get(request_url.clone())
+        .timeout(http_client.settings().feed_timeout);
+    match credentials {
+        Some(c) => c.apply_on_origin(request, feed_url, request_url),
+        None => request,
+    }
+}
+
+/// Sends the request built by `build_request` for the URL, and the ones built for the URLs it is redirected to.
+///
+/// The client used by `build_request` must not follow redirections itself, like `HttpClient::manual_redirect_client`. Following them here lets the requests
+/// be built again for every URL, so that credentials are only sent where they belong.
+/// The redirections followed are appended to `redirects`, even if one of the requests fails.
+pub async fn send_following_redirects<F>(
+    url: &str,
+    build_request: F,
+    http_client: &HttpClient,
+    retry: bool,
+    redirects: &mut Vec<Redirect>,
+) -> Result<Response, Box<dyn Error>>
+where
+    F: Fn(&Url) -> RequestBuilder,
+{
+    let mut current_url = Url::parse(url)?;
+    loop {
+        let request = build_request(&current_url);
+        let response = if retry {
+            http_client.settings().send_with_retry(request).await?
+        } else {
+            request.send().await?
+        };
+        let is_redirection = matches!(
+            response.status(),
+            StatusCode::MOVED_PERMANENTLY
+                | StatusCode::FOUND
+                | StatusCode::SEE_OTHER
+                | StatusCode::TEMPORARY_REDIRECT
+                | StatusCode::PERMANENT_REDIRECT
+        );
+        if !is_redirection {
+            return Ok(response);
+        }
+        if redirects.len() == MAX_REDIRECTS {
+            return Err(format!("Too many redirections (more than {})", MAX_REDIRECTS).into());
+        }
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .ok_or("Redirection without a Location header")?
+            .to_str()?;
+        current_url = current_url.join(location)?;
+        redirects.push(Redirect {
+            url: current_url.clone(),
+            status: response.status(),
+        });
+    }
+}
+
+/// Returns the path of the file the URL points to, if it uses the `file` scheme
+pub fn local_file_path(url: &str) -> Option<PathBuf> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() != "file" {
+        return None;
+    }
+    url.to_file_path().ok()
+}
+
+/// Fetches the feed, falling back on its cached copy if it cannot be downloaded.
+///
+/// Feeds stored on the local filesystem are read directly and never cached.
+/// In offline mode, the network is not used at all.
+/// If the feed announced that it moved, the move is returned along with the channel.
+pub async fn get_feed<'a>(
+    url: &'a str,
+    feed_cache: &FeedCache,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+    offline: bool,
+) -> Option<(ChannelTuple<'a>, FeedSource, Option<FeedMove>)> {
+    if let Some(path) = local_file_path(url) {
+        return match read_local_feed(url, &path) {
+            Ok(t) => {
+                let feed_move = FeedMove::detect(url, &t.1, None);
+                Some((t, FeedSource::Local, feed_move))
+            }
+            Err(e) => {
+                log::error!("Could not read local feed {} : {}", path.display(), e);
+                None
+            }
+        };
+    }
+    if !offline {
+        match get_feed_inner(url, feed_cache, credentials, http_client).await {
+            Ok((t, permanent_redirect)) => {
+                let feed_move = FeedMove::detect(url, &t.1, permanent_redirect.as_deref());
+                return Some((t, FeedSource::Network, feed_move));
+            }
+            Err(e) => log::error!("Could not load rss feed : {}", e),
+        }
+    }
+    match read_cached_feed(url, feed_cache) {
+        Ok(t) => {
+            info!("Using cached copy of feed on URL {}", url);
+            let feed_move = FeedMove::detect(url, &t.1, None);
+            Some((t, FeedSource::Cache, feed_move))
+        }
         Err(e) => {
-            log::error!("Could not load rss feed : {}", e);
+            log::error!("Could not read cached copy of feed on URL {} : {}", url, e);
             None
         }
     }
 }
-async fn get_feed_inner(url: &str) -> Result<ChannelTuple, Box<dyn Error>> {
+
+/// Fetches all the feeds whose URLs are given in parameter, in parallel.
+///
+/// No more than `max_parallel_fetches` feeds are downloaded at the same time.
+/// Returns the fetched channels, and the URLs of the feeds that could not be fetched.
+pub async fn get_feeds<'a>(
+    urls: &[&'a str],
+    feed_cache: &FeedCache,
+    credentials: &BTreeMap<String, FeedCredentials>,
+    http_client: &HttpClient,
+    offline: bool,
+) -> (
+    Vec<(ChannelTuple<'a>, FeedSource, Option<FeedMove>)>,
+    Vec<String>,
+) {
+    let feeds: tokio::sync::Mutex<Vec<(ChannelTuple, FeedSource, Option<FeedMove>)>> =
+        tokio::sync::Mutex::new(vec![]);
+    let faulty_feeds: tokio::sync::Mutex<Vec<String>> = tokio::sync::Mutex::new(vec![]);
+    let fetch_permits =
+        tokio::sync::Semaphore::new(http_client.settings().max_parallel_fetches.max(1));
+
+    let mut get_feed_futures = vec![];
+
+    for f in urls {
+        get_feed_futures.push(async {
+            let _permit = fetch_permits
+                .acquire()
+                .await
+                .expect("Semaphore of feed fetches closed");
+            let feed = get_feed(f, feed_cache, credentials.get(*f), http_client, offline).await;
+            if let Some(f) = feed {
+                feeds.lock().await.push(f);
+            } else {
+                faulty_feeds.lock().await.push(f.to_string());
+            }
+            /* Lol ??? */
+            if false {
+                return Err(());
+            }
+            Ok(())
+        });
+    }
+    try_join_all(get_feed_futures)
+        .await
+        .expect("Future parallelized retrieving failed");
+
+    let feeds: Vec<(ChannelTuple, FeedSource, Option<FeedMove>)> = feeds.into_inner();
+    let faulty_feeds: Vec<String> = faulty_feeds.into_inner();
+    (feeds, faulty_feeds)
+}
+
+fn read_cached_feed<'a>(
+    url: &'a str,
+    feed_cache: &FeedCache,
+) -> Result<ChannelTuple<'a>, Box<dyn Error>> {
+    let content = feed_cache.read_content(url)?;
+    let channel = Feed::read_from(&content[..])?;
+    Ok((url, channel))
+}
+
+/// Reads a feed stored on the local filesystem, or builds one if the path is the one of a directory
+fn read_local_feed<'a>(url: &'a str, path: &Path) -> Result<ChannelTuple<'a>, Box<dyn Error>> {
+    if path.is_dir() {
+        return Ok((url, read_local_directory(path)?));
+    }
+    let content = fs::read(path)?;
+    let channel = Feed::read_from(&content[..])?;
+    Ok((url, channel))
+}
+
+/// Downloads the feed, sending the validators of the cached copy if there is one.
+///
+/// If the server answers that the feed has not been modified, the cached copy is used instead.
+/// The target of the redirections followed is returned as well if all of them were permanent.
+async fn get_feed_inner<'a>(
+    url: &'a str,
+    feed_cache: &FeedCache,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+) -> Result<(ChannelTuple<'a>, Option<String>), Box<dyn Error>> {
     info!("Downloading feed on URL {}", url);
-    let content = reqwest::get(url).await?.bytes().await?;
-    let channel = Channel::read_from(&content[..])?;
+    let validators = feed_cache.read_validators(url);
+    let feed_url = Url::parse(url)?;
+    let build_request = |request_url: &Url| {
+        let mut request = build_feed_request(http_client, request_url, &feed_url, credentials);
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    };
+    let mut redirects = vec![];
+    let response =
+        send_following_redirects(url, build_request, http_client, true, &mut redirects).await?;
+    let permanent_redirect = match redirects.last() {
+        Some(r) if redirects.iter().all(|r| r.is_permanent()) => Some(r.url.to_string()),
+        _ => None,
+    };
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        info!("Feed on URL {} not modified, using cached copy", url);
+        return Ok((read_cached_feed(url, feed_cache)?, permanent_redirect));
+    }
+
+    let response = response.error_for_status()?;
+    let validators = FeedValidators::from_headers(response.headers());
+    let content = response.bytes().await?;
+    let channel = Feed::read_from(&content[..])?;
+    if let Err(e) = feed_cache.store(url, &content, &validators) {
+        warn!("Caching of feed on URL {} failed : {}", url, e);
+    }
     info!("Building of channel for URL {} done", url);
-    Ok((url, channel))
+    Ok(((url, channel), permanent_redirect))
+}
+
+/// Downloads the feed without using the cache, and reports every step of its retrieval.
+///
+/// Returns the parsed feed along with the report if it could be retrieved.
+/// Requests are not retried, so that transient failures are reported as well.
+pub async fn diagnose_feed(
+    url: &str,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+) -> (FeedDiagnostic, Option<Feed>) {
+    let mut diagnostic = FeedDiagnostic::new(url);
+    match diagnose_feed_inner(url, credentials, http_client, &mut diagnostic).await {
+        Ok(feed) => {
+            diagnostic.item_count = Some(feed.item_count());
+            (diagnostic, Some(feed))
+        }
+        Err(e) => {
+            diagnostic.error = Some(e.to_string());
+            (diagnostic, None)
+        }
+    }
+}
+
+async fn diagnose_feed_inner(
+    url: &str,
+    credentials: Option<&FeedCredentials>,
+    http_client: &HttpClient,
+    diagnostic: &mut FeedDiagnostic,
+) -> Result<Feed, Box<dyn Error>> {
+    if let Some(path) = local_file_path(url) {
+        return Ok(read_local_feed(url, &path)?.1);
+    }
+    let feed_url = Url::parse(url)?;
+    let build_request =
+        |request_url: &Url| build_feed_request(http_client, request_url, &feed_url, credentials);
+    let mut redirects = vec![];
+    let result =
+        send_following_redirects(url, build_request, http_client, false, &mut redirects).await;
+    diagnostic.redirects = redirects.iter().map(|r| r.url.to_string()).collect();
+    diagnostic.status = match &result {
+        Ok(response) => Some(response.status().as_u16()),
+        Err(_) => redirects.last().map(|r| r.status.as_u16()),
+    };
+    let response = result?;
+
+    diagnostic.content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_owned());
+    let response = response.error_for_status()?;
+    let content = response.bytes().await?;
+    Feed::read_from(&content[..])
 }
 
 #[cfg(test)]
@@ -32,16 +338,101 @@ mod tests {
 
     use super::*;
 
+    use test_case::test_case;
+
+    fn fixture_url(file_name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join(file_name);
+        reqwest::Url::from_file_path(path).unwrap().to_string()
+    }
+
+    #[test_case("rss_feed.xml" => ("Local show".to_owned(), 2); "RSS feed")]
+    #[test_case("atom_feed.xml" => ("Local atom show".to_owned(), 1); "Atom feed")]
+    fn test_get_local_feed(file_name: &str) -> (String, usize) {
+        let url = fixture_url(file_name);
+        let cache_dir = std::env::temp_dir().join("test_get_local_feed_cache");
+        let feed_cache = FeedCache::new(cache_dir.clone());
+
+        // Local feeds are read even in offline mode, and never cached
+        let (channel, source, feed_move) = aw!(get_feed(
+            &url,
+            &feed_cache,
+            None,
+            &HttpClient::default(),
+            true
+        ))
+        .unwrap();
+        assert_eq!(source, FeedSource::Local);
+        assert!(feed_move.is_none());
+        assert!(feed_cache.read_content(&url).is_err());
+        (channel.1.title().to_owned(), channel.1.item_count())
+    }
+
+    #[test]
+    fn test_get_missing_local_feed() {
+        let url = fixture_url("missing_feed.xml");
+        let feed_cache = FeedCache::new(std::env::temp_dir().join("test_get_local_feed_cache"));
+        assert!(aw!(get_feed(
+            &url,
+            &feed_cache,
+            None,
+            &HttpClient::default(),
+            false
+        ))
+        .is_none());
+    }
+
     #[test]
-    fn test_get_channel() -> Result<(), String> {
-        // TODO : When I'll be motivated, prefer launching a webserver locally in order to make these tests independant from any online third-party
-        let url: &str = "https://www.lemonde.fr/rss/une.xml";
-        let channel = aw!(get_feed_inner(url));
-        if let Err(_) = channel {
-            return Err(String::from("Test failed"));
-        }
-        let channel: ChannelTuple = channel.unwrap();
-        assert_eq!(channel.1.link(), url);
+    fn test_diagnose_local_feed() {
+        let (diagnostic, feed) = aw!(diagnose_feed(
+            &fixture_url("rss_feed.xml"),
+            None,
+            &HttpClient::default()
+        ));
+        assert!(feed.is_some());
+        assert!(diagnostic.error.is_none());
+        assert_eq!(diagnostic.item_count, Some(2));
+    }
+
+    #[test_case("https://www.example.com/feed.xml" => None; "HTTP URL")]
+    #[test_case("file:///tmp/feed.xml" => Some(PathBuf::from("/tmp/feed.xml")); "File URL")]
+    #[test_case("/tmp/feed.xml" => None; "Path")]
+    fn test_local_file_path(url: &str) -> Option<PathBuf> {
+        local_file_path(url)
+    }
+
+    #[test]
+    fn test_get_feed_offline() -> Result<(), String> {
+        let url: &str = "https://www.example.com/offline_feed.xml";
+        let cache_dir = std::env::temp_dir().join("test_get_feed_offline_cache");
+        let feed_cache = FeedCache::new(cache_dir.clone());
+
+        assert!(aw!(get_feed(
+            url,
+            &feed_cache,
+            None,
+            &HttpClient::default(),
+            true
+        ))
+        .is_none());
+
+        let content = b"<rss version=\"2.0\"><channel><title>Offline</title><link>https://www.example.com</link><description>Cached</description></channel></rss>";
+        feed_cache
+            .store(url, content, &FeedValidators::default())
+            .expect("Storing in cache failed");
+        let (channel, source, _) = aw!(get_feed(
+            url,
+            &feed_cache,
+            None,
+            &HttpClient::default(),
+            true
+        ))
+        .unwrap();
+        assert_eq!(source, FeedSource::Cache);
+        assert_eq!(channel.1.title(), "Offline");
+
+        std::fs::remove_dir_all(cache_dir).expect("Cleanup of test failed");
         Ok(())
     }
 }
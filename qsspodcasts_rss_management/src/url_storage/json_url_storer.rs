@@ -0,0 +1,347 @@
+use super::file_url_storer::FileUrlStorer;
+use super::subscription::Subscription;
+pub use super::url_storer::UrlStorer;
+
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+const CURRENT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SubscriptionFile {
+    version: u32,
+    subscriptions: Vec<Subscription>,
+}
+
+impl Default for SubscriptionFile {
+    fn default() -> Self {
+        SubscriptionFile {
+            version: CURRENT_VERSION,
+            subscriptions: vec![],
+        }
+    }
+}
+
+/// Stores subscriptions and their metadata in a versioned JSON file.
+///
+/// If the file does not exist yet, subscriptions are imported from the legacy one-URL-per-line file,
+/// which is left untouched.
+pub struct JsonUrlStorer {
+    file_path: PathBuf,
+    legacy_file_path: PathBuf,
+}
+
+impl JsonUrlStorer {
+    pub fn new(file_path: PathBuf, legacy_file_path: PathBuf) -> JsonUrlStorer {
+        JsonUrlStorer {
+            file_path,
+            legacy_file_path,
+        }
+    }
+
+    /// Writes a subscription file without any subscription
+    pub fn create_empty_file(file_path: &PathBuf) -> Result<(), io::Error> {
+        JsonUrlStorer::write_file(file_path, &SubscriptionFile::default())
+    }
+
+    fn write_file(file_path: &PathBuf, file: &SubscriptionFile) -> Result<(), io::Error> {
+        let content = serde_json::to_string_pretty(file)?;
+        fs::write(file_path, content)
+    }
+
+    fn migrate_legacy_file(&self) -> Result<SubscriptionFile, io::Error> {
+        let mut legacy_storer = FileUrlStorer::new(self.legacy_file_path.clone());
+        let subscriptions = legacy_storer
+            .get_urls()?
+            .iter()
+            .map(|u| u.trim())
+            .filter(|u| !u.is_empty())
+            .map(Subscription::new)
+            .collect::<Vec<Subscription>>();
+        info!(
+            "Migrating {} subscriptions from {}",
+            subscriptions.len(),
+            self.legacy_file_path.display()
+        );
+        let file = SubscriptionFile {
+            version: CURRENT_VERSION,
+            subscriptions,
+        };
+        JsonUrlStorer::write_file(&self.file_path, &file)?;
+        Ok(file)
+    }
+
+    /// Upgrades the content of a file written by a previous version of the application
+    fn upgrade(file: SubscriptionFile) -> Result<SubscriptionFile, io::Error> {
+        match file.version {
+            CURRENT_VERSION => Ok(file),
+            v => Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported subscription file version {}", v),
+            )),
+        }
+    }
+
+    fn read_file(&self) -> Result<SubscriptionFile, io::Error> {
+        if !self.file_path.exists() {
+            if self.legacy_file_path.exists() {
+                return self.migrate_legacy_file();
+            }
+            return Ok(SubscriptionFile::default());
+        }
+        let content = fs::read_to_string(&self.file_path)?;
+        let file: SubscriptionFile = serde_json::from_str(&content)?;
+        JsonUrlStorer::upgrade(file)
+    }
+
+    fn update_subscription<F>(&self, url: &str, update: F) -> Result<(), io::Error>
+    where
+        F: FnOnce(&mut Subscription),
+    {
+        let mut file = self.read_file()?;
+        match file.subscriptions.iter_mut().find(|s| s.url == url) {
+            Some(subscription) => update(subscription),
+            None => return Err(io::Error::from(ErrorKind::NotFound)),
+        }
+        JsonUrlStorer::write_file(&self.file_path, &file)
+    }
+}
+
+impl UrlStorer for JsonUrlStorer {
+    fn write_url(&mut self, url: &str) -> Result<(), io::Error> {
+        let mut file = self.read_file()?;
+        if file.subscriptions.iter().any(|s| s.url == url) {
+            return Err(io::Error::from(ErrorKind::AlreadyExists));
+        }
+        file.subscriptions.push(Subscription::new(url));
+        JsonUrlStorer::write_file(&self.file_path, &file)
+    }
+
+    fn delete_url(&mut self, url: &str) -> Result<(), io::Error> {
+        let mut file = self.read_file()?;
+        file.subscriptions.retain(|s| s.url != url);
+        JsonUrlStorer::write_file(&self.file_path, &file)
+    }
+
+    fn get_urls(&mut self) -> Result<Vec<String>, io::Error> {
+        Ok(self
+            .read_file()?
+            .subscriptions
+            .into_iter()
+            .map(|s| s.url)
+            .collect())
+    }
+
+    fn get_subscriptions(&mut self) -> Result<Vec<Subscription>, io::Error> {
+        Ok(self.read_file()?.subscriptions)
+    }
+
+    fn replace_url(&mut self, old_url: &str, new_url: &str) -> Result<(), io::Error> {
+        let mut file = self.read_file()?;
+        if file.subscriptions.iter().any(|s| s.url == new_url) {
+            return Err(io::Error::from(ErrorKind::AlreadyExists));
+        }
+        match file.subscriptions.iter_mut().find(|s| s.url == old_url) {
+            Some(subscription) => subscription.url = new_url.to_owned(),
+            None => return Err(io::Error::from(ErrorKind::NotFound)),
+        }
+        JsonUrlStorer::write_file(&self.file_path, &file)
+    }
+
+    fn record_successful_fetches(&mut self, urls: &[&str]) -> Result<(), io::Error> {
+        if urls.is_empty() {
+            return Ok(());
+        }
+        let mut file = self.read_file()?;
+        let now = Utc::now();
+        file.subscriptions
+            .iter_mut()
+            .filter(|s| urls.contains(&s.url.as_str()))
+            .for_each(|s| s.last_successful_fetch = Some(now));
+        JsonUrlStorer::write_file(&self.file_path, &file)
+    }
+
+    fn set_alias(&mut self, url: &str, alias: Option<&str>) -> Result<(), io::Error> {
+        self.update_subscription(url, |s| s.alias = alias.map(|a| a.to_owned()))
+    }
+
+    fn set_paused(&mut self, url: &str, paused: bool) -> Result<(), io::Error> {
+        self.update_subscription(url, |s| s.paused = paused)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestFiles {
+        file_path: PathBuf,
+        legacy_file_path: PathBuf,
+    }
+
+    impl TestFiles {
+        fn new(name: &str) -> TestFiles {
+            let dir = std::env::temp_dir();
+            TestFiles {
+                file_path: dir.join(format!("{}.json", name)),
+                legacy_file_path: dir.join(format!("{}_legacy", name)),
+            }
+        }
+
+        fn build_storer(&self) -> JsonUrlStorer {
+            JsonUrlStorer::new(self.file_path.clone(), self.legacy_file_path.clone())
+        }
+    }
+
+    impl Drop for TestFiles {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.file_path);
+            let _ = fs::remove_file(&self.legacy_file_path);
+        }
+    }
+
+    #[test]
+    fn test_write_and_delete_url() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_write");
+        let mut storer = files.build_storer();
+        storer.write_url("https://www.example.com/feed").unwrap();
+        storer
+            .write_url("https://www.example.com/feed.xml")
+            .unwrap();
+        assert_eq!(
+            storer
+                .write_url("https://www.example.com/feed")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::AlreadyExists
+        );
+
+        // Only the exact URL is deleted, even if it is a substring of another one
+        storer.delete_url("https://www.example.com/feed").unwrap();
+        assert_eq!(
+            storer.get_urls().unwrap(),
+            vec!["https://www.example.com/feed.xml"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_url() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_replace");
+        let mut storer = files.build_storer();
+        storer
+            .write_url("https://old.example.com/feed.xml")
+            .unwrap();
+        storer
+            .write_url("https://other.example.com/feed.xml")
+            .unwrap();
+        let date_added = storer.get_subscriptions().unwrap()[0].date_added;
+
+        storer
+            .replace_url(
+                "https://old.example.com/feed.xml",
+                "https://new.example.com/feed.xml",
+            )
+            .unwrap();
+        let subscriptions = storer.get_subscriptions().unwrap();
+        assert_eq!(subscriptions[0].url, "https://new.example.com/feed.xml");
+        assert_eq!(subscriptions[0].date_added, date_added);
+
+        assert_eq!(
+            storer
+                .replace_url(
+                    "https://new.example.com/feed.xml",
+                    "https://other.example.com/feed.xml"
+                )
+                .unwrap_err()
+                .kind(),
+            ErrorKind::AlreadyExists
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_legacy_file() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_migration");
+        fs::write(
+            &files.legacy_file_path,
+            "https://www.example.com/1.xml\n\nhttps://www.example.com/2.xml\n",
+        )
+        .unwrap();
+
+        let mut storer = files.build_storer();
+        assert_eq!(
+            storer.get_urls().unwrap(),
+            vec![
+                "https://www.example.com/1.xml",
+                "https://www.example.com/2.xml"
+            ]
+        );
+        assert!(files.file_path.exists());
+        assert!(files.legacy_file_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_successful_fetches() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_fetches");
+        let mut storer = files.build_storer();
+        storer.write_url("https://www.example.com/1.xml").unwrap();
+        storer.write_url("https://www.example.com/2.xml").unwrap();
+        storer
+            .record_successful_fetches(&["https://www.example.com/2.xml"])
+            .unwrap();
+
+        let subscriptions = storer.get_subscriptions().unwrap();
+        assert!(subscriptions[0].last_successful_fetch.is_none());
+        assert!(subscriptions[1].last_successful_fetch.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_alias_and_paused() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_metadata");
+        let mut storer = files.build_storer();
+        storer.write_url("https://www.example.com/1.xml").unwrap();
+        storer
+            .set_alias("https://www.example.com/1.xml", Some("My podcast"))
+            .unwrap();
+        storer
+            .set_paused("https://www.example.com/1.xml", true)
+            .unwrap();
+
+        let subscriptions = storer.get_subscriptions().unwrap();
+        assert_eq!(subscriptions[0].alias.as_deref(), Some("My podcast"));
+        assert!(subscriptions[0].paused);
+
+        storer
+            .set_alias("https://www.example.com/1.xml", None)
+            .unwrap();
+        assert!(storer.get_subscriptions().unwrap()[0].alias.is_none());
+        assert_eq!(
+            storer
+                .set_paused("https://www.example.com/2.xml", true)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotFound
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_version() -> Result<(), String> {
+        let files = TestFiles::new("test_json_url_storer_version");
+        fs::write(&files.file_path, r#"{"version": 42, "subscriptions": []}"#).unwrap();
+        assert_eq!(
+            files.build_storer().get_urls().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+        Ok(())
+    }
+}
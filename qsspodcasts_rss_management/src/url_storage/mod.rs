@@ -1,2 +1,4 @@
 pub mod file_url_storer;
+pub mod json_url_storer;
+pub mod subscription;
 pub mod url_storer;
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A subscribed feed, along with the metadata the user and the application keep about it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub url: String,
+    pub date_added: DateTime<Utc>,
+    /// Name chosen by the user, displayed instead of the title of the feed
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Paused subscriptions are not fetched
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub last_successful_fetch: Option<DateTime<Utc>>,
+}
+
+impl Subscription {
+    pub fn new(url: &str) -> Subscription {
+        Subscription {
+            url: url.to_owned(),
+            date_added: Utc::now(),
+            alias: None,
+            paused: false,
+            last_successful_fetch: None,
+        }
+    }
+}
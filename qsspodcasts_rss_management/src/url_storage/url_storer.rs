@@ -1,6 +1,43 @@
-use std::io;
+use super::subscription::Subscription;
+use std::io::{self, ErrorKind};
 pub trait UrlStorer {
     fn write_url(&mut self, url: &str) -> Result<(), io::Error>;
     fn delete_url(&mut self, url: &str) -> Result<(), io::Error>;
     fn get_urls(&mut self) -> Result<Vec<String>, io::Error>;
+
+    /// Returns subscriptions along with their metadata.
+    ///
+    /// Storers that do not keep any metadata return default ones.
+    fn get_subscriptions(&mut self) -> Result<Vec<Subscription>, io::Error> {
+        Ok(self
+            .get_urls()?
+            .iter()
+            .map(|u| Subscription::new(u))
+            .collect())
+    }
+
+    /// Replaces the URL of a subscription, keeping its metadata when the storer has some
+    fn replace_url(&mut self, old_url: &str, new_url: &str) -> Result<(), io::Error> {
+        self.delete_url(old_url)?;
+        self.write_url(new_url)
+    }
+
+    fn record_successful_fetches(&mut self, _urls: &[&str]) -> Result<(), io::Error> {
+        Ok(())
+    }
+
+    /// Sets the name displayed instead of the title of the feed, or removes it if there is none
+    fn set_alias(&mut self, _url: &str, _alias: Option<&str>) -> Result<(), io::Error> {
+        Err(io::Error::new(
+            ErrorKind::Unsupported,
+            "Subscriptions metadata is not stored",
+        ))
+    }
+
+    fn set_paused(&mut self, _url: &str, _paused: bool) -> Result<(), io::Error> {
+        Err(io::Error::new(
+            ErrorKind::Unsupported,
+            "Subscriptions metadata is not stored",
+        ))
+    }
 }